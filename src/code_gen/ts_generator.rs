@@ -4,6 +4,10 @@ use crate::{
         open_api_javascript::OpenApiJavaScriptParser,
         parser_tools::{OpenApiModule, OpenApiRequester},
     },
+    tools::ts_escape::{
+        escape_doc_comment, escape_line_comment, escape_template_literal, ts_identifier,
+        ts_string_literal,
+    },
 };
 use lazy_static::lazy_static;
 use log::info;
@@ -22,7 +26,11 @@ pub fn create_typescript_api(
 ) {
     init_workspace(command_config);
     create_default_resource_file(command_config);
+    create_params_file(command_config);
     create_ts_d_ts(command_config, open_api_parser);
+    create_runtime_file(command_config, "guards.ts", open_api_parser.get_type_guard_list());
+    create_runtime_file(command_config, "enums.ts", open_api_parser.get_enum_list());
+    create_converter_file(command_config, open_api_parser);
     create_entry_file(command_config, open_api_parser);
     create_controller(command_config, open_api_parser);
 }
@@ -45,6 +53,7 @@ pub fn create_entry_file(
     // 创建并打开入口文件
     let mut f = OpenOptions::new()
         .create(true)
+        .truncate(false)
         .read(true)
         .write(true)
         .open(entry_file_path)
@@ -66,7 +75,7 @@ pub fn create_entry_file(
     };
     let old_module_list: Vec<String> = old_content
         .split_whitespace()
-        .filter_map(|v| get_module_name(v))
+        .filter_map(&get_module_name)
         .collect();
 
     // 写入入口文件内容
@@ -80,7 +89,36 @@ pub fn create_entry_file(
             }
         }
         f.write_all(write_content.as_bytes())
-            .expect(format!("{} write error", write_content).as_str());
+            .unwrap_or_else(|_| panic!("{} write error", write_content));
+    }
+
+    // 多个模块中重复生成的接口只从第一个模块导出，避免 export * 重名
+    for write_content in create_duplicated_export_list(command_config, open_api_parser) {
+        if old_content.contains(write_content.trim()) {
+            continue;
+        }
+        f.write_all(write_content.as_bytes())
+            .unwrap_or_else(|_| panic!("{} write error", write_content));
+    }
+
+    // 导出运行时代码
+    let runtime_list = [
+        ("type guards", "guards", open_api_parser.get_type_guard_list()),
+        ("enums", "enums", open_api_parser.get_enum_list()),
+    ];
+    for (desc, file_name, content_list) in runtime_list {
+        if content_list.is_empty() || old_module_list.contains(&String::from(file_name)) {
+            continue;
+        }
+        f.write_all(
+            format!(
+                r#"// {desc}
+export * from './{file_name}';
+"#
+            )
+            .as_bytes(),
+        )
+        .unwrap_or_else(|_| panic!("{} export write error", file_name));
     }
 }
 
@@ -97,12 +135,7 @@ fn create_controller(
     let mut module_path_map = HashMap::new();
 
     for module in open_api_parser.get_module_list() {
-        let module_name = &module.description;
-        let module_name = module_name
-            .split_whitespace()
-            .collect::<Vec<&str>>()
-            .join("");
-        let module_dir_path = controller_dir_path.as_path().join(module_name);
+        let module_dir_path = controller_dir_path.as_path().join(&module.dir_name);
         if let Some(module_dir_path) = module_dir_path.to_str() {
             module_path_map.insert(module.name, String::from(module_dir_path));
         }
@@ -111,18 +144,35 @@ fn create_controller(
     // 文件拆分模式，优先创建 module文件夹
     if command_config.split {
         // 创建module文件夹
-        for (_, dir) in &module_path_map {
-            fs::create_dir_all(&dir).expect(format!("create module {} dir error", &dir).as_str());
+        for dir in module_path_map.values() {
+            fs::create_dir_all(dir).unwrap_or_else(|_| panic!("create module {} dir error", &dir));
         }
     }
 
     let mut file_touched_record_map = HashMap::new();
 
+    // 非拆分模式下 模块文件中所有api调用用到的参数处理方法
+    let mut module_helpers_map: HashMap<&String, Vec<&str>> = HashMap::new();
     for (module, request) in open_api_parser.get_api_list() {
+        if request.reexport_module.is_some() {
+            continue;
+        }
+        let helpers = module_helpers_map.entry(module).or_default();
+        for helper in create_api_helpers(request) {
+            if !helpers.contains(&helper) {
+                helpers.push(helper);
+            }
+        }
+    }
+
+    for (module, request) in open_api_parser.get_api_list() {
+        if request.reexport_module.is_some() {
+            continue;
+        }
         if let Some(module_path) = module_path_map.get(module) {
-            let operation_id = &request.operation_id;
+            let operation_id = &ts_identifier(&request.operation_id);
             info!("generate call {}  ", operation_id);
-            let api_template = create_api_call(&request);
+            let api_template = create_api_call(request);
             let file_end = ".ts";
 
             // 生成写入文件路径
@@ -157,14 +207,14 @@ fn create_controller(
                     OpenOptions::new()
                         .append(true)
                         .open(module_entry_path)
-                        .expect(format!("{} open error", module_entry_path_str_key).as_str())
+                        .unwrap_or_else(|_| panic!("{} open error", module_entry_path_str_key))
                 } else {
                     OpenOptions::new()
                         .create(true)
                         .truncate(true)
                         .write(true)
                         .open(module_entry_path)
-                        .expect(format!("{} open error", module_entry_path_str_key).as_str())
+                        .unwrap_or_else(|_| panic!("{} open error", module_entry_path_str_key))
                 };
 
                 // 记录module入口文件是否被打开过
@@ -177,12 +227,12 @@ fn create_controller(
                     .write_all(
                         {
                             let mut content = format!(r#"export * from "./{operation_id}";"#);
-                            content.push_str("\n");
+                            content.push('\n');
                             content
                         }
                         .as_bytes(),
                     )
-                    .expect(format!("{} write error", module_entry_path_str_key).as_str());
+                    .unwrap_or_else(|_| panic!("{} write error", module_entry_path_str_key));
             }
 
             let file_path_str_key = if let Some(file_path_str_key) = file_path.clone().to_str() {
@@ -203,18 +253,23 @@ fn create_controller(
                 OpenOptions::new()
                     .append(true)
                     .open(file_path)
-                    .expect(format!("{} open error", operation_id).as_str())
+                    .unwrap_or_else(|_| panic!("{} open error", operation_id))
             } else {
                 OpenOptions::new()
                     .create(true)
                     .truncate(true)
                     .write(true)
                     .open(file_path)
-                    .expect(format!("{} open error", operation_id).as_str())
+                    .unwrap_or_else(|_| panic!("{} open error", operation_id))
             };
 
             let api_template = if command_config.split || !file_touched {
-                let mut api_import_temp = create_api_import(command_config);
+                let helpers = if command_config.split {
+                    create_api_helpers(request)
+                } else {
+                    module_helpers_map.get(module).cloned().unwrap_or_default()
+                };
+                let mut api_import_temp = create_api_import(command_config, &helpers);
                 api_import_temp.push_str(&api_template);
                 api_import_temp
             } else {
@@ -228,34 +283,114 @@ fn create_controller(
 
             // 写入内容
             f.write_all(api_template.as_bytes())
-                .expect(format!("{} write error", operation_id).as_str());
+                .unwrap_or_else(|_| panic!("{} write error", operation_id));
         }
     }
+
+    // 多个 tag 的接口在其他 tag 的模块中重新导出
+    for (module, request) in open_api_parser.get_api_list() {
+        let (Some(module_path), Some(reexport_path)) = (
+            module_path_map.get(module),
+            request.reexport_module.as_ref().and_then(|v| module_path_map.get(v)),
+        ) else {
+            continue;
+        };
+        let operation_id = ts_identifier(&request.operation_id);
+        let reexport_dir_name = Path::new(reexport_path)
+            .file_name()
+            .and_then(|v| v.to_str())
+            .unwrap_or_default();
+        let (file_path, content) = if command_config.split {
+            (
+                Path::new(module_path).join("index.ts"),
+                format!(r#"export {{ {operation_id} }} from "../{reexport_dir_name}/{operation_id}";"#),
+            )
+        } else {
+            (
+                PathBuf::from(format!("{}.ts", module_path)),
+                format!(r#"
+export {{ {operation_id} }} from "./{reexport_dir_name}";"#),
+            )
+        };
+        let file_path_str_key = file_path.to_string_lossy().to_string();
+        let file_touched = file_touched_record_map
+            .get(&file_path_str_key)
+            .copied()
+            .unwrap_or(false);
+        let mut f = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(file_touched)
+            .truncate(!file_touched)
+            .open(&file_path)
+            .unwrap_or_else(|_| panic!("{} open error", file_path_str_key));
+        file_touched_record_map.insert(file_path_str_key.clone(), true);
+        f.write_all(format!("{content}\n").as_bytes())
+            .unwrap_or_else(|_| panic!("{} write error", file_path_str_key));
+    }
 }
 
 /// 生成接口导出项
 fn create_entry_export_template(controller_dir_name: &str, tag: &OpenApiModule) -> String {
-    let module_name = &tag.description;
-    let module_name = module_name
-        .split_whitespace()
-        .collect::<Vec<&str>>()
-        .join("");
-
-    let module = module_name;
-    let desc = &tag.description;
-    String::from(format!(
+    let module = &tag.dir_name;
+    let desc = escape_line_comment(&tag.description);
+    format!(
         r#"// {desc} 
 export * from './{controller_dir_name}/{module}';
 "#
-    ))
+    )
+}
+
+/// 生成多个模块中重复生成的接口的导出语句
+///
+/// --multi-tag duplicate 时同一个接口在多个模块中生成，入口文件中 export * 重名会报错，
+/// 显式导出的名称优先于 export *，所以显式从第一个模块导出重名的接口
+fn create_duplicated_export_list(
+    command_config: &CommandConfig,
+    open_api_parser: &impl OpenApiJavaScriptParser,
+) -> Vec<String> {
+    let dir_name_map: HashMap<String, String> = open_api_parser
+        .get_module_list()
+        .into_iter()
+        .map(|v| (v.name, v.dir_name))
+        .collect();
+    let mut first_module_map: HashMap<String, &String> = HashMap::new();
+    let mut duplicated_list: Vec<(String, &String)> = vec![];
+    for (module, request) in open_api_parser.get_api_list() {
+        if request.reexport_module.is_some() {
+            continue;
+        }
+        let operation_id = ts_identifier(&request.operation_id);
+        match first_module_map.get(&operation_id) {
+            Some(first_module) => {
+                if !first_module.eq(&module) && !duplicated_list.iter().any(|v| v.0.eq(&operation_id)) {
+                    duplicated_list.push((operation_id, first_module));
+                }
+            }
+            None => {
+                first_module_map.insert(operation_id, module);
+            }
+        }
+    }
+    duplicated_list
+        .into_iter()
+        .filter_map(|(operation_id, module)| {
+            let dir_name = dir_name_map.get(module)?;
+            Some(format!(
+                "export {{ {operation_id} }} from './{}/{dir_name}';\n",
+                command_config.controller_dir_name
+            ))
+        })
+        .collect()
 }
 
 /// 生成api调用
 fn create_api_call(open_api_request: &OpenApiRequester) -> String {
     // 接口调用名
-    let operation_id = &open_api_request.operation_id;
+    let operation_id = ts_identifier(&open_api_request.operation_id);
     // 接口说明
     let summary = &open_api_request.summary;
+    let summary_doc = escape_doc_comment(summary, "");
     // 响应类型
     let response_type = &open_api_request.response_type_name;
     let request_type =  if open_api_request.is_form {
@@ -264,7 +399,7 @@ fn create_api_call(open_api_request: &OpenApiRequester) -> String {
         open_api_request.request_type_name.clone()
     };
     let method = &open_api_request.method;
-    let api_url = &open_api_request.url;
+    let api_url = ts_string_literal(&open_api_request.url);
     let form_name = if open_api_request.is_form {
         String::from("Form")
     } else {
@@ -273,31 +408,216 @@ fn create_api_call(open_api_request: &OpenApiRequester) -> String {
     let method_name = if summary.starts_with("[No Auth]") {
         format!("{method}{}NoAuth", form_name)
     } else {
-        format!("{}{}", method.to_string(), form_name)
+        format!("{}{}", method, form_name)
     };
-    format!(
-        r#"
+
+    // 响应值需要转换时 在响应后转换
+    let converter = if let Some(response_converter) = &open_api_request.response_converter {
+        format!(".then((res) => {response_converter})")
+    } else {
+        String::new()
+    };
+
+    // 请求体中的 bigint 在发送前转换
+    let body = if open_api_request.serialize_body {
+        "toJsonBody(req)"
+    } else {
+        "req"
+    };
+
+    let parameters = &open_api_request.parameters;
+    let has_query = parameters.iter().any(|v| v.parameter_in.eq("query"));
+    let has_header = parameters
+        .iter()
+        .any(|v| v.parameter_in.eq("header") || v.parameter_in.eq("cookie"));
+    let has_path = parameters.iter().any(|v| v.parameter_in.eq("path"));
+
+    match &open_api_request.parameters_type_name {
+        // 参数与请求体分开传入
+        Some(parameters_type) => {
+            let (destructure, url, config_value) =
+                create_api_parameters(open_api_request, "params", parameters_type, has_query);
+            let url = if has_query {
+                format!("withQuery({url}, query)")
+            } else {
+                url
+            };
+            format!(
+                r#"
 /**
- * {summary}
+ * {summary_doc}
+ */
+export function {operation_id}(params:{parameters_type}, req:{request_type}, config?: RequestConfig): Promise<{response_type}> {{
+    {destructure}
+    return resource.{method_name}({url}, {body}, {config_value}){converter};
+}}
+    "#
+            )
+        }
+        // 参数全部在 req 中，只需要拆出地址、header、cookie参数
+        None if has_path || has_header => {
+            let (destructure, url, config_value) =
+                create_api_parameters(open_api_request, "req", &request_type, true);
+            format!(
+                r#"
+/**
+ * {summary_doc}
  */
 export function {operation_id}(req:{request_type}, config?: RequestConfig): Promise<{response_type}> {{
-    return resource.{method_name}("{api_url}", req, config);
+    {destructure}
+    return resource.{method_name}({url}, query, {config_value}){converter};
 }}
     "#
-    )
+            )
+        }
+        None => format!(
+            r#"
+/**
+ * {summary_doc}
+ */
+export function {operation_id}(req:{request_type}, config?: RequestConfig): Promise<{response_type}> {{
+    return resource.{method_name}({api_url}, {body}, config){converter};
+}}
+    "#
+        ),
+    }
+}
+
+/// 生成参数的解构语句、请求地址以及请求配置
+///
+/// 地址参数编码后拼接进地址，header、cookie参数合并进请求配置，
+/// with_query 为 true 时其余参数解构为 query
+fn create_api_parameters(
+    open_api_request: &OpenApiRequester,
+    source: &str,
+    source_type: &str,
+    with_query: bool,
+) -> (String, String, String) {
+    let mut taken = vec![
+        String::from("req"),
+        String::from("params"),
+        String::from("query"),
+        String::from("config"),
+        String::from("resource"),
+    ];
+    let mut fields = vec![];
+    let mut url = escape_template_literal(&open_api_request.url);
+    let mut headers = vec![];
+    let mut cookies = vec![];
+    for parameter in &open_api_request.parameters {
+        if parameter.parameter_in.eq("query") {
+            continue;
+        }
+        let variable = create_variable_name(&parameter.name, &taken);
+        taken.push(variable.clone());
+        let key = ts_string_literal(&parameter.name);
+        fields.push(format!("{key}: {variable}"));
+        match parameter.parameter_in.as_str() {
+            "path" => {
+                url = url.replace(
+                    &format!("{{{}}}", escape_template_literal(&parameter.name)),
+                    &format!("${{encodeURIComponent(stringifyParam({variable}))}}"),
+                );
+            }
+            "header" => headers.push(format!("{key}: {variable}")),
+            "cookie" => cookies.push(format!("{key}: {variable}")),
+            _ => {}
+        }
+    }
+    if with_query {
+        fields.push(String::from("...query"));
+    }
+    // 参数可选时 req 可能为空
+    let source_value = if source_type.ends_with("| void") {
+        format!("({source} || {{}}) as {}", source_type.trim_end_matches("| void").trim())
+    } else {
+        String::from(source)
+    };
+    let destructure = format!("const {{ {} }} = {source_value};", fields.join(", "));
+    let mut config_value = String::from("config");
+    if !headers.is_empty() {
+        config_value = format!("withHeaders({config_value}, {{ {} }})", headers.join(", "));
+    }
+    if !cookies.is_empty() {
+        config_value = format!("withCookies({config_value}, {{ {} }})", cookies.join(", "));
+    }
+    (destructure, format!("`{url}`"), config_value)
+}
+
+/// 获取api调用中用到的参数处理方法
+fn create_api_helpers(open_api_request: &OpenApiRequester) -> Vec<&'static str> {
+    let parameters = &open_api_request.parameters;
+    let mut helpers = vec![];
+    if open_api_request.parameters_type_name.is_some()
+        && parameters.iter().any(|v| v.parameter_in.eq("query"))
+    {
+        helpers.push("withQuery");
+    }
+    if parameters.iter().any(|v| v.parameter_in.eq("header")) {
+        helpers.push("withHeaders");
+    }
+    if parameters.iter().any(|v| v.parameter_in.eq("cookie")) {
+        helpers.push("withCookies");
+    }
+    if parameters.iter().any(|v| v.parameter_in.eq("path")) {
+        helpers.push("stringifyParam");
+    }
+    if open_api_request.serialize_body {
+        helpers.push("toJsonBody");
+    }
+    if open_api_request.response_converter.is_some() {
+        helpers.push("converters");
+    }
+    helpers
+}
+
+/// 生成参数对应的变量名称
+///
+/// 如：X-Request-Id 则返回 xRequestId，与已有变量或关键字重名时添加 _ 前缀
+fn create_variable_name(name: &str, taken: &[String]) -> String {
+    let mut chars = name.chars();
+    let name: String = match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    };
+    let mut variable = ts_identifier(&name);
+    while taken.contains(&variable) {
+        variable = format!("_{}", variable);
+    }
+    variable
 }
 
 /// 创建api调用文件中的导入内容
-fn create_api_import(command_config: &CommandConfig) -> String {
+fn create_api_import(command_config: &CommandConfig, helpers: &[&str]) -> String {
     let import_path = if command_config.split {
         "../../"
     } else {
         "../"
     };
-    format!(
+    let mut import = format!(
         r#"import {{ resource, RequestConfig }} from "{import_path}helper/resource";
 "#
-    )
+    );
+    let mut params_helpers: Vec<&str> = helpers
+        .iter()
+        .filter(|v| !v.eq(&&"converters"))
+        .copied()
+        .collect();
+    if !params_helpers.is_empty() {
+        params_helpers.sort();
+        import.push_str(&format!(
+            r#"import {{ {} }} from "{import_path}helper/params";
+"#,
+            params_helpers.join(", ")
+        ));
+    }
+    if helpers.contains(&"converters") {
+        import.push_str(&format!(
+            r#"import * as converters from "{import_path}helper/converters";
+"#
+        ));
+    }
+    import
 }
 
 /// 生成typescript类型文件
@@ -321,18 +641,85 @@ fn create_ts_d_ts(command_config: &CommandConfig, open_api_parser: &mut impl Ope
             )
             .expect("ts .d write error");
     }
+    // 类型映射中声明的类型
+    let mut type_mapping_vec: Vec<_> = command_config.type_mapping.iter().collect();
+    type_mapping_vec.sort_by(|a, b| a.0.cmp(b.0));
+    for declare in type_mapping_vec.iter().filter_map(|v| v.1.declare()) {
+        ts_d_f
+            .write_all(format!("{declare}\n\n").as_bytes())
+            .expect("ts .d write error");
+    }
     for value in open_api_parser.get_interface_enum_list(&command_config.ignore_option) {
         ts_d_f
             .write_all(value.as_bytes())
-            .expect(format!("{} td type write error", value).as_str());
+            .unwrap_or_else(|_| panic!("{} td type write error", value));
     }
-    if let Some(_) = &command_config.namespace {
+    if command_config.namespace.is_some() {
         ts_d_f
-            .write_all(format!(r#"}}"#,).as_bytes())
+            .write_all(b"}")
             .expect("ts .d write error");
     }
 }
 
+/// 生成运行时代码文件，如类型守卫、枚举
+///
+/// 内容为空时不生成文件
+fn create_runtime_file(command_config: &CommandConfig, file_name: &str, content_list: Vec<String>) {
+    if content_list.is_empty() {
+        return;
+    }
+    info!("create {}", file_name);
+    let workspace_path = Path::new(&command_config.workspace);
+    let mut runtime_f = OpenOptions::new()
+        .create(true)
+        .truncate(true)
+        .write(true)
+        .open(workspace_path.join(file_name))
+        .unwrap_or_else(|_| panic!("{} open error", file_name));
+    for value in content_list {
+        runtime_f
+            .write_all(value.as_bytes())
+            .unwrap_or_else(|_| panic!("{} write error", value));
+    }
+}
+
+/// 生成响应值转换文件 helper/converters.ts
+///
+/// 没有需要转换的响应时不生成
+fn create_converter_file(
+    command_config: &CommandConfig,
+    open_api_parser: &impl OpenApiJavaScriptParser,
+) {
+    let converter_list = open_api_parser.get_converter_list();
+    if converter_list.is_empty() {
+        return;
+    }
+    let mut content_list = vec![String::from(
+        r#"
+export function mapValue(value: any, convert: (value: any) => any): any {
+    return value === undefined || value === null ? value : convert(value);
+}
+
+export function mapArray(value: any, convert: (item: any) => any): any {
+    return Array.isArray(value) ? value.map(convert) : value;
+}
+
+export function mapRecord(value: any, convert: (item: any) => any): any {
+    if (value === undefined || value === null) {
+        return value;
+    }
+    const result: Record<string, any> = {};
+    Object.keys(value).forEach((key) => {
+        result[key] = convert(value[key]);
+    });
+    return result;
+}
+"#,
+    )];
+    content_list.extend(converter_list);
+    create_runtime_file(command_config, "helper/converters.ts", content_list);
+}
+
 /// 创建默认的调用文件
 fn create_default_resource_file(command_config: &CommandConfig) {
     let workspace_path = Path::new(&command_config.workspace);
@@ -343,6 +730,7 @@ fn create_default_resource_file(command_config: &CommandConfig) {
         info!("create default resource file");
         let mut resource_file = OpenOptions::new()
             .create(true)
+            .truncate(true)
             .write(true)
             .open(resource_file_path)
             .expect("resource file open error");
@@ -411,6 +799,26 @@ class Resource {
         console.log("please impl put");
         return Promise.resolve();
     }
+
+    patch<T>(url: string, req: RequestParam, config?: RequestConfig): Promise<any> {
+        console.log("please impl patch");
+        return Promise.resolve();
+    }
+
+    head<T>(url: string, req: RequestParam, config?: RequestConfig): Promise<any> {
+        console.log("please impl head");
+        return Promise.resolve();
+    }
+
+    options<T>(url: string, req: RequestParam, config?: RequestConfig): Promise<any> {
+        console.log("please impl options");
+        return Promise.resolve();
+    }
+
+    trace<T>(url: string, req: RequestParam, config?: RequestConfig): Promise<any> {
+        console.log("please impl trace");
+        return Promise.resolve();
+    }
 }
 
 export const resource = new Resource();"#
@@ -419,3 +827,205 @@ export const resource = new Resource();"#
             .expect("resource file write error");
     }
 }
+
+/// 创建参数处理文件，每次生成时覆盖
+fn create_params_file(command_config: &CommandConfig) {
+    info!("create params file");
+    let helper_dir_path = Path::new(&command_config.workspace).join("helper");
+    fs::create_dir_all(&helper_dir_path).expect("create helper dir error");
+    let mut params_file = OpenOptions::new()
+        .create(true)
+        .truncate(true)
+        .write(true)
+        .open(helper_dir_path.join("params.ts"))
+        .expect("params file open error");
+    params_file
+        .write_all(
+            r#"import { RequestConfig } from "./resource";
+
+/**
+ * 将参数值转换为字符串，Date 使用 ISO 8601 格式
+ */
+export function stringifyParam(value: unknown): string {
+    return value instanceof Date ? value.toISOString() : String(value);
+}
+
+/**
+ * 将请求体中的 bigint 转换为字符串，JSON.stringify 无法序列化 bigint
+ */
+export function toJsonBody<T>(value: T): T {
+    if (typeof value === "bigint") {
+        return String(value) as unknown as T;
+    }
+    if (Array.isArray(value)) {
+        return value.map((item) => toJsonBody(item)) as unknown as T;
+    }
+    if (value !== null && typeof value === "object" && Object.getPrototypeOf(value) === Object.prototype) {
+        const result: Record<string, unknown> = {};
+        Object.keys(value).forEach((key) => {
+            result[key] = toJsonBody((value as Record<string, unknown>)[key]);
+        });
+        return result as T;
+    }
+    return value;
+}
+
+/**
+ * 将 query 参数拼接到请求地址中，忽略值为 undefined 或 null 的参数
+ */
+export function withQuery(url: string, query: Record<string, unknown>): string {
+    const search: string[] = [];
+    Object.keys(query).forEach((key) => {
+        const value = query[key];
+        const values = Array.isArray(value) ? value : [value];
+        values.forEach((v) => {
+            if (v !== undefined && v !== null) {
+                search.push(`${encodeURIComponent(key)}=${encodeURIComponent(stringifyParam(v))}`);
+            }
+        });
+    });
+    if (search.length === 0) {
+        return url;
+    }
+    return `${url}${url.includes("?") ? "&" : "?"}${search.join("&")}`;
+}
+
+/**
+ * 将 header 参数合并到请求配置中，忽略值为 undefined 或 null 的参数
+ */
+export function withHeaders(config: RequestConfig | undefined, headers: Record<string, unknown>): RequestConfig {
+    const result: RequestConfig = { ...config };
+    Object.keys(headers).forEach((key) => {
+        const value = headers[key];
+        if (value !== undefined && value !== null) {
+            result[key] = stringifyParam(value);
+        }
+    });
+    return result;
+}
+
+/**
+ * 将 cookie 参数合并到请求配置的 Cookie 中，忽略值为 undefined 或 null 的参数
+ */
+export function withCookies(config: RequestConfig | undefined, cookies: Record<string, unknown>): RequestConfig {
+    const result: RequestConfig = { ...config };
+    const cookie = Object.keys(cookies)
+        .filter((key) => cookies[key] !== undefined && cookies[key] !== null)
+        .map((key) => `${key}=${encodeURIComponent(stringifyParam(cookies[key]))}`);
+    if (cookie.length > 0) {
+        result["Cookie"] = [result["Cookie"], ...cookie].filter(Boolean).join("; ");
+    }
+    return result;
+}
+"#
+            .as_bytes(),
+        )
+        .expect("params file write error");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::open_parser::parser_tools::OpenApiParameter;
+
+    fn requester(method: &str, url: &str, parameters: &[(&str, &str)]) -> OpenApiRequester {
+        OpenApiRequester {
+            summary: String::new(),
+            operation_id: String::from("callApi"),
+            url: url.to_string(),
+            method: method.to_string(),
+            request_schema_name: String::from("void"),
+            request_type_name: String::from("void"),
+            response_type_name: String::from("void"),
+            is_form: false,
+            parameters: parameters
+                .iter()
+                .map(|(name, parameter_in)| OpenApiParameter {
+                    name: name.to_string(),
+                    parameter_in: parameter_in.to_string(),
+                })
+                .collect(),
+            parameters_schema_name: None,
+            parameters_type_name: None,
+            response_converter: None,
+            serialize_body: false,
+            reexport_module: None,
+        }
+    }
+
+    #[test]
+    fn path_parameter_call_test() {
+        let mut request = requester("get", "/users/{id}", &[("id", "path")]);
+        request.request_type_name = String::from("CallApiQuery");
+        let api_call = create_api_call(&request);
+        assert!(api_call.contains(r#"const { "id": id, ...query } = req;"#));
+        assert!(api_call.contains(
+            "return resource.get(`/users/${encodeURIComponent(stringifyParam(id))}`, query, config);"
+        ));
+
+        // 没有 query 参数时不解构 query
+        let mut request = requester("post", "/users/{id}", &[("id", "path")]);
+        request.request_type_name = String::from("User");
+        request.parameters_type_name = Some(String::from("CallApiQuery"));
+        let api_call = create_api_call(&request);
+        assert!(api_call.contains(
+            "export function callApi(params:CallApiQuery, req:User, config?: RequestConfig)"
+        ));
+        assert!(api_call.contains(r#"const { "id": id } = params;"#));
+        assert!(api_call.contains(
+            "return resource.post(`/users/${encodeURIComponent(stringifyParam(id))}`, req, config);"
+        ));
+        assert_eq!(create_api_helpers(&request), vec!["stringifyParam"]);
+    }
+
+    #[test]
+    fn query_and_body_call_test() {
+        let mut request = requester("post", "/items", &[("page", "query")]);
+        request.request_type_name = String::from("Item");
+        request.parameters_type_name = Some(String::from("CallApiQuery | void"));
+        request.serialize_body = true;
+        let api_call = create_api_call(&request);
+        assert!(api_call.contains("const { ...query } = (params || {}) as CallApiQuery;"));
+        assert!(api_call.contains(
+            "return resource.post(withQuery(`/items`, query), toJsonBody(req), config);"
+        ));
+        assert_eq!(
+            create_api_helpers(&request),
+            vec!["withQuery", "toJsonBody"]
+        );
+    }
+
+    #[test]
+    fn header_call_test() {
+        let mut request = requester(
+            "get",
+            "/items",
+            &[("X-Request-Id", "header"), ("page", "query")],
+        );
+        request.request_type_name = String::from("CallApiQuery");
+        let api_call = create_api_call(&request);
+        assert!(api_call.contains(r#"const { "X-Request-Id": xRequestId, ...query } = req;"#));
+        assert!(api_call.contains(
+            r#"return resource.get(`/items`, query, withHeaders(config, { "X-Request-Id": xRequestId }));"#
+        ));
+        assert!(!api_call.contains("withCookies"));
+        assert_eq!(create_api_helpers(&request), vec!["withHeaders"]);
+
+        let mut request = requester(
+            "post",
+            "/items",
+            &[("X-Request-Id", "header"), ("session", "cookie")],
+        );
+        request.parameters_type_name = Some(String::from("CallApiQuery"));
+        let api_call = create_api_call(&request);
+        assert!(api_call
+            .contains(r#"const { "X-Request-Id": xRequestId, "session": session } = params;"#));
+        assert!(api_call.contains(
+            r#"withCookies(withHeaders(config, { "X-Request-Id": xRequestId }), { "session": session })"#
+        ));
+        assert_eq!(
+            create_api_helpers(&request),
+            vec!["withHeaders", "withCookies"]
+        );
+    }
+}
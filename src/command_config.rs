@@ -1,6 +1,13 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 
-use crate::tools::tools::capitalize;
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+use crate::tools::{
+    http_request::HttpConfig,
+    module_name::load_module_name_map,
+    tools::capitalize,
+    type_mapping::{load_type_mapping, TypeMapping},
+};
 
 #[derive(Debug, Clone)]
 pub struct CommandConfig {
@@ -13,6 +20,61 @@ pub struct CommandConfig {
     pub operation_prefix: Option<String>,
     pub namespace: Option<String>,
     pub wrap: Option<String>,
+    pub http: HttpConfig,
+    pub enum_style: EnumStyle,
+    pub inline_object: InlineObjectStyle,
+    pub type_mapping: HashMap<String, TypeMapping>,
+    pub flatten_generics: bool,
+    pub name_collision_suffix: String,
+    pub default_module: String,
+    pub multi_tag: MultiTagStyle,
+    pub module_naming: ModuleNaming,
+    pub module_name_map: HashMap<String, String>,
+    pub all_schemas: bool,
+}
+
+/// 枚举的生成方式
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumStyle {
+    /// 只生成字面量联合类型
+    Union,
+    /// 额外在 enums.ts 中生成 enum
+    Enum,
+    /// 额外在 enums.ts 中生成 as const 对象
+    Const,
+}
+
+/// 属性中匿名对象的生成方式
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InlineObjectStyle {
+    /// 提升为以 父类型名称+属性名称 命名的 interface
+    Hoist,
+    /// 生成对象字面量类型
+    Literal,
+}
+
+/// 有多个 tag 的接口的生成方式
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiTagStyle {
+    /// 只生成在第一个 tag 的模块中
+    First,
+    /// 在每个 tag 的模块中各生成一份
+    Duplicate,
+    /// 生成在第一个 tag 的模块中，其他 tag 的模块重新导出
+    Reexport,
+}
+
+/// 模块目录的命名方式
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleNaming {
+    /// 去掉空白的 tag 描述
+    Description,
+    /// tag 名称
+    Name,
+    /// 短横线连接的小写 tag 名称，如：user-controller
+    Kebab,
+    /// 小驼峰的 tag 名称，如：userController
+    Camel,
 }
 
 #[derive(Parser, Debug)]
@@ -22,7 +84,7 @@ struct Args {
     #[arg(short, long)]
     output: String,
 
-    /// open api config url
+    /// open api config source (json or yaml): http(s) url, local file path, file:// url or `-` for stdin
     #[arg(short, long)]
     config: String,
 
@@ -45,13 +107,93 @@ struct Args {
     /// wrap response
     #[arg(long)]
     wrap: Option<String>,
+
+    /// extra header sent when fetching the config, e.g. "X-Api-Key: xxx" (repeatable)
+    #[arg(short = 'H', long = "header")]
+    headers: Vec<String>,
+
+    /// env var holding a bearer token used when fetching the config
+    #[arg(long)]
+    bearer_token_env: Option<String>,
+
+    /// env var holding "user:password" for basic auth when fetching the config
+    #[arg(long)]
+    basic_auth_env: Option<String>,
+
+    /// PEM file with extra CA certificates trusted when fetching the config
+    #[arg(long)]
+    ca_cert: Option<PathBuf>,
+
+    /// proxy url used when fetching the config
+    #[arg(long)]
+    proxy: Option<String>,
+
+    /// timeout in seconds when fetching the config
+    #[arg(long)]
+    timeout: Option<u64>,
+
+    /// skip TLS certificate validation when fetching the config
+    #[arg(long, default_value_t = false)]
+    insecure: bool,
+
+    /// how enums are generated: literal unions only, or additionally `enum` / `as const` objects in enums.ts
+    #[arg(long, value_enum, default_value_t = EnumStyle::Union)]
+    enum_style: EnumStyle,
+
+    /// how inline object schemas are generated: hoisted into `ParentNameFieldName` interfaces or inline object literal types
+    #[arg(long, value_enum, default_value_t = InlineObjectStyle::Hoist)]
+    inline_object: InlineObjectStyle,
+
+    /// json or yaml file mapping "type/format" (or "type") to a TypeScript type, e.g. {"string/date-time": "Date"}
+    #[arg(long)]
+    type_mapping: Option<PathBuf>,
+
+    /// flatten Springfox generic schemas such as `Result«User»` into `ResultUser` instead of generating `Result<T>`
+    #[arg(long, default_value_t = false)]
+    flatten_generics: bool,
+
+    /// name used when a generated type name already exists, `{name}` is the original name and `{index}` counts from 2
+    #[arg(long, default_value = "{name}{index}", value_parser = parse_name_collision_suffix)]
+    name_collision_suffix: String,
+
+    /// module of operations without tags
+    #[arg(long, default_value = "default")]
+    default_module: String,
+
+    /// how operations with multiple tags are generated: only in the first tag module, duplicated into each tag module, or re-exported from the first tag module
+    #[arg(long, value_enum, default_value_t = MultiTagStyle::First)]
+    multi_tag: MultiTagStyle,
+
+    /// how module directories are named: tag description without whitespace, tag name, kebab-case or camelCase tag name
+    #[arg(long, value_enum, default_value_t = ModuleNaming::Description)]
+    module_naming: ModuleNaming,
+
+    /// json or yaml file mapping tag names to module directory names, e.g. {"user-controller": "user"}
+    #[arg(long)]
+    module_name_map: Option<PathBuf>,
+
+    /// generate every schema even when --tags is given, instead of only the schemas used by the selected apis
+    #[arg(long, default_value_t = false)]
+    all_schemas: bool,
+}
+
+/// 校验类型名称冲突时使用的名称模板
+fn parse_name_collision_suffix(value: &str) -> Result<String, String> {
+    if !value.contains("{index}") {
+        return Err(String::from("must contain {index}"));
+    }
+    let rest = value.replace("{name}", "").replace("{index}", "");
+    if !rest.chars().all(|v| v.is_ascii_alphanumeric() || v == '_' || v == '$') {
+        return Err(String::from("may only contain letters, digits, `_` and `$` besides {name} and {index}"));
+    }
+    Ok(value.to_string())
 }
 
 pub fn get_command_config() -> CommandConfig {
     let args = Args::parse();
 
-    let operation_prefix = if args.namespace.is_some() {
-       args.namespace.unwrap().split_whitespace().collect::<Vec<&str>>().join("").trim().to_string()
+    let operation_prefix = if let Some(namespace) = &args.namespace {
+       namespace.split_whitespace().collect::<Vec<&str>>().join("").trim().to_string()
     } else {
         String::from("")
     };
@@ -88,10 +230,31 @@ pub fn get_command_config() -> CommandConfig {
         }else {
             Some(capitalize(&operation_prefix).to_string())
         },
-        wrap: if let Some(wrap) = args.wrap {
-            Some(wrap.trim().into())
-        }else {
-           None
+        wrap: args.wrap.map(|wrap| wrap.trim().into()),
+        http: HttpConfig {
+            headers: args.headers,
+            bearer_token_env: args.bearer_token_env,
+            basic_auth_env: args.basic_auth_env,
+            ca_cert: args.ca_cert,
+            proxy: args.proxy,
+            timeout: args.timeout.map(Duration::from_secs),
+            insecure: args.insecure,
         },
+        enum_style: args.enum_style,
+        inline_object: args.inline_object,
+        type_mapping: args
+            .type_mapping
+            .map(|v| load_type_mapping(&v).expect("type mapping load error"))
+            .unwrap_or_default(),
+        flatten_generics: args.flatten_generics,
+        name_collision_suffix: args.name_collision_suffix,
+        default_module: args.default_module,
+        multi_tag: args.multi_tag,
+        module_naming: args.module_naming,
+        module_name_map: args
+            .module_name_map
+            .map(|v| load_module_name_map(&v).expect("module name map load error"))
+            .unwrap_or_default(),
+        all_schemas: args.all_schemas,
     }
 }
@@ -3,7 +3,12 @@ mod command_config;
 mod open_api;
 mod open_parser;
 mod tools;
-use crate::{code_gen::ts_generator, open_parser::open_api_javascript::OpenApi3JavaScript};
+use crate::{
+    code_gen::ts_generator,
+    open_api::open_api_loader::load_open_api_config,
+    open_parser::open_api_javascript::OpenApi3JavaScript,
+    tools::config_source::ConfigSource,
+};
 use command_config::get_command_config;
 
 #[tokio::main]
@@ -13,10 +18,21 @@ async fn main() {
     // 获取命令行参数
     let command_config = get_command_config();
 
-    // 获取open api 配置文件内容
-    let mut open_config = tools::http_request::get(&command_config.open_config_path)
+    // 获取open api 配置文件内容 支持http地址、本地文件以及标准输入
+    // 文件格式支持 json 与 yaml，swagger 2.0 文档会被升级为 open api 3
+    let config_source = ConfigSource::detect(&command_config.open_config_path);
+    let config_content = config_source
+        .read(&command_config.http)
         .await
         .expect("open api config get error");
+    let mut open_config = load_open_api_config(
+        &config_source,
+        &config_content,
+        &command_config.http,
+        &command_config.name_collision_suffix,
+    )
+    .await
+    .expect("open api config parse error");
 
     // 生成 typescript open api 调用
     ts_generator::create_typescript_api(
@@ -1 +1,4 @@
-pub mod open_api_3;
\ No newline at end of file
+pub mod open_api_3;
+pub mod open_api_loader;
+pub mod ref_resolver;
+pub mod swagger_2;
\ No newline at end of file
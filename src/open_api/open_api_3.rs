@@ -1,13 +1,22 @@
 use std::collections::HashMap;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Open3Config {
     pub components: Open3Components,
     pub paths: HashMap<String, Open3Requests>,
+    #[serde(default)]
     pub tags: Vec<Open3Tag>,
+    pub servers: Option<Vec<Open3Server>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Open3Server {
+    pub url: String,
+
+    pub description: Option<String>,
 }
 
 type Open3ApiConfigOption = Option<Open3ApiConfig>;
@@ -18,10 +27,17 @@ pub struct Open3Requests {
     pub post: Open3ApiConfigOption,
     pub put: Open3ApiConfigOption,
     pub delete: Open3ApiConfigOption,
+    pub patch: Open3ApiConfigOption,
+    pub head: Open3ApiConfigOption,
+    pub options: Open3ApiConfigOption,
+    pub trace: Open3ApiConfigOption,
+
+    // 路径下所有请求共用的参数
+    pub parameters: Option<Vec<Open3Parameters>>,
 }
 
 impl Open3Requests {
-    pub fn iter(&self) -> Open3RequestsIntoIterator {
+    pub fn iter(&self) -> Open3RequestsIntoIterator<'_> {
         Open3RequestsIntoIterator {
             config: self,
             index: 0,
@@ -42,6 +58,10 @@ impl<'a> Iterator for Open3RequestsIntoIterator<'a> {
             1 => (String::from("post"), &self.config.post),
             2 => (String::from("put"), &self.config.put),
             3 => (String::from("delete"), &self.config.delete),
+            4 => (String::from("patch"), &self.config.patch),
+            5 => (String::from("head"), &self.config.head),
+            6 => (String::from("options"), &self.config.options),
+            7 => (String::from("trace"), &self.config.trace),
             _ => return None,
         };
         self.index += 1;
@@ -51,8 +71,8 @@ impl<'a> Iterator for Open3RequestsIntoIterator<'a> {
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Open3ApiConfig {
-    // 操作名称
-    #[serde(alias = "operationId")]
+    // 操作名称 缺少时使用请求方式与地址生成
+    #[serde(alias = "operationId", default)]
     pub operation_id: String,
 
     // 响应配置
@@ -64,21 +84,85 @@ pub struct Open3ApiConfig {
 
     pub summary: Option<String>,
 
+    // 缺少时放入默认模块
+    #[serde(default)]
     pub tags: Vec<String>,
 
     pub parameters: Option<Vec<Open3Parameters>>,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct Open3Parameters {
+    // 引用 components.parameters 中的参数
+    #[serde(alias = "$ref")]
+    pub parameter_ref: Option<String>,
+
+    #[serde(default)]
     pub name: String,
 
-    #[serde(alias = "in")]
+    #[serde(alias = "in", default)]
     pub parameters_in: String,
 
     pub description: Option<String>,
 
+    #[serde(default)]
     pub required: bool,
+
+    pub schema: Option<Open3Schema>,
+
+    // 使用 content 声明参数结构时 只会有一个媒体类型
+    pub content: Option<HashMap<String, Open3ResponseContent>>,
+}
+
+/// 可以合并的参数，name 与 in 都相同的参数视为同一个参数
+pub trait MergeParameter {
+    /// 返回参数的 name 与 in
+    fn parameter_key(&self) -> (&str, &str);
+}
+
+/// 合并路径参数与请求参数，并解析参数引用
+///
+/// 请求中 name 与 in 相同的参数会覆盖路径中的参数，解析失败的参数被忽略
+pub fn merge_parameters<T: MergeParameter>(
+    path_parameters: &Option<Vec<T>>,
+    parameters: &Option<Vec<T>>,
+    resolve: impl Fn(&T) -> Option<T>,
+) -> Vec<T> {
+    let mut merged: Vec<T> = vec![];
+    let resolved = path_parameters
+        .iter()
+        .flatten()
+        .chain(parameters.iter().flatten())
+        .filter_map(resolve);
+    for parameter in resolved {
+        if let Some(exist) = merged
+            .iter_mut()
+            .find(|v| v.parameter_key() == parameter.parameter_key())
+        {
+            *exist = parameter;
+        } else {
+            merged.push(parameter);
+        }
+    }
+    merged
+}
+
+impl MergeParameter for Open3Parameters {
+    fn parameter_key(&self) -> (&str, &str) {
+        (&self.name, &self.parameters_in)
+    }
+}
+
+impl Open3Parameters {
+    /// 获取参数的 schema，未声明时返回 None
+    pub fn get_schema(&self) -> Option<&Open3Schema> {
+        self.schema.as_ref().or_else(|| {
+            self.content
+                .as_ref()
+                .and_then(|content| content.values().next())
+                .map(|v| &v.schema)
+        })
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -86,15 +170,22 @@ pub struct Open3RequestBody {
     pub content: HashMap<String, Open3ResponseContent>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
-pub struct Open3RequestBodyContent {
-    pub schema: Open3RequestBodyContentSchema,
-}
-
-#[derive(Deserialize, Serialize, Debug)]
-pub struct Open3RequestBodyContentSchema {
-    #[serde(alias = "$ref")]
-    pub schema_ref: Option<String>,
+impl Open3RequestBody {
+    /// 根据媒体类型获取请求体内容
+    ///
+    /// 忽略媒体类型的参数与大小写，如 application/json;charset=UTF-8 视为 application/json
+    pub fn get_content(&self, media_type: &str) -> Option<&Open3ResponseContent> {
+        self.content
+            .iter()
+            .find(|(key, _)| {
+                key.split(';')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .eq_ignore_ascii_case(media_type)
+            })
+            .map(|(_, content)| content)
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -115,8 +206,9 @@ pub struct Open3Schema {
     pub schema_ref: Option<String>,
 
     #[serde(alias = "type")]
-    pub schema_type: Option<String>,
+    pub schema_type: Option<Open3SchemaType>,
 
+    #[serde(default, deserialize_with = "deserialize_items")]
     pub items: Option<Box<Open3Schema>>,
 
     #[serde(alias = "enum")]
@@ -131,11 +223,219 @@ pub struct Open3Schema {
     pub properties: Option<HashMap<String, Open3Schema>>,
 
     pub required: Option<Vec<String>>,
+
+    // 3.1 常量值
+    #[serde(alias = "const")]
+    pub const_value: Option<Value>,
+
+    // 3.1 元组类型
+    #[serde(alias = "prefixItems")]
+    pub prefix_items: Option<Vec<Open3Schema>>,
+
+    // 3.1 内嵌的 schema 定义
+    #[serde(alias = "$defs")]
+    pub defs: Option<HashMap<String, Open3Schema>>,
+
+    pub example: Option<Value>,
+
+    // 3.1 示例列表
+    pub examples: Option<Vec<Value>>,
+
+    // 组合类型 需同时满足所有 schema
+    #[serde(alias = "allOf")]
+    pub all_of: Option<Vec<Open3Schema>>,
+
+    // 组合类型 只满足其中一个 schema
+    #[serde(alias = "oneOf")]
+    pub one_of: Option<Vec<Open3Schema>>,
+
+    // 组合类型 满足其中任意 schema
+    #[serde(alias = "anyOf")]
+    pub any_of: Option<Vec<Open3Schema>>,
+
+    // 3.0 可为空
+    pub nullable: Option<bool>,
+
+    // 只出现在响应中
+    #[serde(alias = "readOnly")]
+    pub read_only: Option<bool>,
+
+    // 只出现在请求中
+    #[serde(alias = "writeOnly")]
+    pub write_only: Option<bool>,
+
+    // 未声明属性的类型 可以是布尔值或 schema
+    #[serde(alias = "additionalProperties")]
+    pub additional_properties: Option<Open3AdditionalProperties>,
+
+    // 枚举成员名称
+    #[serde(alias = "x-enum-varnames")]
+    pub x_enum_varnames: Option<Vec<String>>,
+
+    // 枚举成员说明
+    #[serde(alias = "x-enum-descriptions")]
+    pub x_enum_descriptions: Option<Vec<String>>,
+
+    // oneOf anyOf 中用于区分类型的属性
+    pub discriminator: Option<Open3Discriminator>,
+
+    // 类型映射后的 ts 类型，不属于 open api 文档
+    #[serde(skip)]
+    pub ts_type: Option<String>,
+
+    // 类型映射后响应值的转换表达式，不属于 open api 文档
+    #[serde(skip)]
+    pub ts_convert: Option<String>,
+
+    // 泛型类型的类型参数，不属于 open api 文档
+    #[serde(skip)]
+    pub type_parameters: Option<Vec<String>>,
+
+    // 是否是泛型类型的实例（如 Result«User»），不生成类型声明，不属于 open api 文档
+    #[serde(skip)]
+    pub generic_instance: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Open3Discriminator {
+    // 用于区分类型的属性名称
+    #[serde(alias = "propertyName")]
+    pub property_name: String,
+
+    // 属性值与 schema 引用的对应关系，未声明的 schema 使用 schema 名称作为属性值
+    pub mapping: Option<HashMap<String, String>>,
+}
+
+/// additionalProperties 可以是布尔值或 schema
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum Open3AdditionalProperties {
+    Bool(bool),
+    Schema(Box<Open3Schema>),
+}
+
+/// schema 类型
+///
+/// 3.1 中 type 可以是数组，如 ["string", "null"]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Open3SchemaType {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl Open3SchemaType {
+    /// 获取所有的类型
+    pub fn types(&self) -> Vec<&str> {
+        match self {
+            Open3SchemaType::Single(schema_type) => vec![schema_type.as_str()],
+            Open3SchemaType::Multiple(schema_types) => {
+                schema_types.iter().map(|v| v.as_str()).collect()
+            }
+        }
+    }
+
+    /// 判断是否包含指定类型
+    pub fn contains(&self, schema_type: &str) -> bool {
+        self.types().contains(&schema_type)
+    }
+}
+
+impl From<&str> for Open3SchemaType {
+    fn from(schema_type: &str) -> Self {
+        Open3SchemaType::Single(schema_type.to_string())
+    }
+}
+
+/// 3.1 中 items 可以是布尔值，布尔值视为未声明 items
+fn deserialize_items<'de, D>(deserializer: D) -> Result<Option<Box<Open3Schema>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum SchemaOrBool {
+        Schema(Box<Open3Schema>),
+        #[allow(dead_code)]
+        Bool(bool),
+    }
+    Ok(match Option::<SchemaOrBool>::deserialize(deserializer)? {
+        Some(SchemaOrBool::Schema(schema)) => Some(schema),
+        Some(SchemaOrBool::Bool(_)) | None => None,
+    })
+}
+
+impl Open3Schema {
+    /// 遍历当前 schema 以及其下所有的子 schema
+    pub fn walk_mut(&mut self, f: &mut impl FnMut(&mut Open3Schema)) {
+        f(self);
+        if let Some(items) = &mut self.items {
+            items.walk_mut(f);
+        }
+        if let Some(properties) = &mut self.properties {
+            for property in properties.values_mut() {
+                property.walk_mut(f);
+            }
+        }
+        if let Some(prefix_items) = &mut self.prefix_items {
+            for item in prefix_items.iter_mut() {
+                item.walk_mut(f);
+            }
+        }
+        if let Some(defs) = &mut self.defs {
+            for def in defs.values_mut() {
+                def.walk_mut(f);
+            }
+        }
+        if let Some(Open3AdditionalProperties::Schema(additional_properties)) =
+            &mut self.additional_properties
+        {
+            additional_properties.walk_mut(f);
+        }
+        for composition in [&mut self.all_of, &mut self.one_of, &mut self.any_of] {
+            for schema in composition.iter_mut().flatten() {
+                schema.walk_mut(f);
+            }
+        }
+    }
+
+    /// 判断是否是组合类型
+    pub fn is_composition(&self) -> bool {
+        self.all_of.is_some() || self.one_of.is_some() || self.any_of.is_some()
+    }
+
+    /// 遍历 schema 以及嵌套的所有 schema
+    pub fn walk(&self, f: &mut impl FnMut(&Open3Schema)) {
+        f(self);
+        if let Some(items) = &self.items {
+            items.walk(f);
+        }
+        for property in self.properties.iter().flat_map(|v| v.values()) {
+            property.walk(f);
+        }
+        for item in self.prefix_items.iter().flatten() {
+            item.walk(f);
+        }
+        for def in self.defs.iter().flat_map(|v| v.values()) {
+            def.walk(f);
+        }
+        if let Some(Open3AdditionalProperties::Schema(additional_properties)) =
+            &self.additional_properties
+        {
+            additional_properties.walk(f);
+        }
+        for composition in [&self.all_of, &self.one_of, &self.any_of] {
+            for schema in composition.iter().flatten() {
+                schema.walk(f);
+            }
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Open3Tag {
     // tag 描述
+    #[serde(default)]
     pub description: String,
     // tag 名称
     pub name: String,
@@ -144,16 +444,9 @@ pub struct Open3Tag {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Open3Components {
     pub schemas: HashMap<String, Open3ComponentsSchema>,
-}
-
-#[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct Open3ComponentsSchema {
-    pub title: Option<String>,
 
-    #[serde(alias = "type")]
-    pub schema_type: String,
-
-    pub properties: Option<HashMap<String, Open3Schema>>,
-
-    pub required: Option<Vec<String>>,
+    pub parameters: Option<HashMap<String, Open3Parameters>>,
 }
+
+/// components 中的 schema 与属性的 schema 结构一致
+pub type Open3ComponentsSchema = Open3Schema;
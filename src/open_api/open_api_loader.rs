@@ -0,0 +1,87 @@
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::{open_api_3::Open3Config, ref_resolver::RefResolver, swagger_2::Swagger2Config};
+use crate::tools::{
+    config_source::{ConfigContent, ConfigSource},
+    document::parse_document,
+    http_request::HttpConfig,
+};
+
+/// 加载 open api 配置文件
+///
+/// 先解析文档中的 $ref 引用（包括外部文档），
+/// swagger 2.0 文档会再升级为 open api 3 文档，后续流程与 open api 3 一致，
+/// suffix_template 为外部文档中的 schema 提升后重名时使用的后缀模板
+pub async fn load_open_api_config(
+    source: &ConfigSource,
+    config_content: &ConfigContent,
+    http_config: &HttpConfig,
+    suffix_template: &str,
+) -> Result<Open3Config> {
+    let document = RefResolver::load(source, config_content, http_config, suffix_template)
+        .await?
+        .resolve()?;
+    let is_swagger_2 = document
+        .get("swagger")
+        .is_some_and(|v| v.to_string().trim_matches('"').starts_with('2'));
+    if is_swagger_2 {
+        let swagger_config: Swagger2Config = deserialize_document(document, config_content)?;
+        return Ok(swagger_config.into_open_3());
+    }
+    deserialize_document(document, config_content)
+}
+
+/// 将解析引用后的文档转换成指定类型
+///
+/// 转换失败时尝试直接解析原始文档，以便错误信息中带有行号和列号，
+/// 原始文档可以解析时（错误出现在引用的内容中）错误信息中带有出错的路径，如：paths./users.get.responses
+fn deserialize_document<T: DeserializeOwned>(
+    document: Value,
+    config_content: &ConfigContent,
+) -> Result<T> {
+    serde_path_to_error::deserialize(document).or_else(|e| {
+        parse_document::<T>(&config_content.content, config_content.format)?;
+        Err(anyhow!(
+            "parse error after resolving $ref at {}: {}",
+            e.path(),
+            e.inner()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::tools::document::DocumentFormat;
+
+    #[test]
+    fn deserialize_error_path_test() {
+        // 原始文档可以解析，引用的内容有误
+        let config_content = ConfigContent {
+            content: String::from(
+                r##"{"components": {"schemas": {"User": {"$ref": "common.json#/User"}}}, "paths": {}}"##,
+            ),
+            format: DocumentFormat::Json,
+        };
+        let document = json!({
+            "components": {
+                "schemas": {
+                    "User": { "type": "object", "properties": { "name": { "required": 5 } } }
+                }
+            },
+            "paths": {}
+        });
+        let error = deserialize_document::<Open3Config>(document, &config_content)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("at components.schemas.User.properties.name.required:"),
+            "{}",
+            error
+        );
+    }
+}
@@ -0,0 +1,627 @@
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::{anyhow, bail, Context, Result};
+use log::info;
+use serde_json::{Map, Value};
+
+use crate::tools::{
+    config_source::{ConfigContent, ConfigSource},
+    document::parse_document,
+    http_request::{is_same_origin, HttpConfig},
+    tools::{capitalize, get_unique_name},
+};
+
+/// 引用解析的结果
+enum ResolvedRef {
+    /// 保留引用，值为改写后的引用地址
+    Keep(String),
+    /// 使用引用的内容替换引用
+    Inline(Value),
+}
+
+/// $ref 引用解析器
+///
+/// 外部文档（本地文件或 url）的地址相对于引用所在的文档解析，每个文档只加载一次。
+///
+/// - 根文档中 components.schemas、components.parameters 以及 $defs 的引用保留
+/// - 外部文档中的 schema 提升到根文档的 components.schemas 中，重名时使用文档名作为前缀，
+///   仍然重名时按照 --name-collision-suffix 生成名称
+/// - 其他引用直接内联，内联时检测循环引用
+pub struct RefResolver {
+    /// 根文档地址
+    root: String,
+    /// 文档地址与文档来源
+    sources: HashMap<String, ConfigSource>,
+    /// 文档地址与文档内容
+    documents: HashMap<String, Value>,
+    /// 根文档是否是 swagger 2.0 文档
+    is_swagger_2: bool,
+    /// 已提升的 schema，key 为 文档地址#json pointer，value 为提升后的名称
+    hoisted: HashMap<String, String>,
+    /// 提升后的 schema 内容
+    hoisted_schemas: Vec<(String, Value)>,
+    /// 正在内联的引用
+    inlining: Vec<String>,
+    /// 名称冲突时的后缀模板
+    suffix_template: String,
+}
+
+impl RefResolver {
+    /// 加载根文档以及其引用的所有外部文档
+    pub async fn load(
+        source: &ConfigSource,
+        config_content: &ConfigContent,
+        http_config: &HttpConfig,
+        suffix_template: &str,
+    ) -> Result<RefResolver> {
+        let root_document: Value = parse_document(&config_content.content, config_content.format)?;
+        let root = source.location();
+        let mut resolver = RefResolver {
+            root: root.clone(),
+            sources: HashMap::from([(root.clone(), source.clone())]),
+            is_swagger_2: root_document.get("swagger").is_some(),
+            documents: HashMap::from([(root.clone(), root_document)]),
+            hoisted: HashMap::new(),
+            hoisted_schemas: vec![],
+            inlining: vec![],
+            suffix_template: suffix_template.to_string(),
+        };
+
+        let mut pending = VecDeque::from([root]);
+        while let Some(location) = pending.pop_front() {
+            let mut references = vec![];
+            collect_refs(&resolver.documents[&location], &mut references);
+            for reference in references {
+                let (document, _) = split_ref(&reference);
+                if document.is_empty() {
+                    continue;
+                }
+                let source = resolver.sources[&location].join(document);
+                let target = source.location();
+                if resolver.documents.contains_key(&target) {
+                    continue;
+                }
+                info!("load $ref document {}", target);
+                // 认证信息与自定义请求头只发送给与根文档同源的地址
+                let target_http_config = if is_same_origin(&resolver.root, &target) {
+                    http_config.clone()
+                } else {
+                    http_config.without_credentials()
+                };
+                let content = source
+                    .read(&target_http_config)
+                    .await
+                    .with_context(|| format!("load $ref {} error", reference))?;
+                let document: Value = parse_document(&content.content, content.format)
+                    .with_context(|| format!("parse $ref document {} error", target))?;
+                resolver.documents.insert(target.clone(), document);
+                resolver.sources.insert(target.clone(), source);
+                pending.push_back(target);
+            }
+        }
+        Ok(resolver)
+    }
+
+    /// 解析根文档中的所有引用，返回解析后的根文档
+    pub fn resolve(mut self) -> Result<Value> {
+        let root = self.root.clone();
+        let mut document = self.documents[&root].clone();
+        self.resolve_value(&root, &mut document)?;
+
+        // 提升的 schema 写入根文档
+        if !self.hoisted_schemas.is_empty() {
+            let mut schemas = &mut document;
+            for key in self.schemas_path() {
+                schemas = object_entry(schemas, key)?;
+            }
+            let schemas = schemas
+                .as_object_mut()
+                .ok_or_else(|| anyhow!("{} is not an object", self.schemas_path().join(".")))?;
+            for (name, schema) in self.hoisted_schemas.drain(..) {
+                schemas.insert(name, schema);
+            }
+        }
+        Ok(document)
+    }
+
+    /// 根文档中 schema 的位置
+    fn schemas_path(&self) -> Vec<&'static str> {
+        if self.is_swagger_2 {
+            vec!["definitions"]
+        } else {
+            vec!["components", "schemas"]
+        }
+    }
+
+    fn resolve_value(&mut self, location: &str, value: &mut Value) -> Result<()> {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(reference)) = map.get("$ref") {
+                    let reference = reference.clone();
+                    match self.resolve_ref(location, &reference)? {
+                        ResolvedRef::Keep(reference) => {
+                            map.insert(String::from("$ref"), Value::String(reference));
+                        }
+                        ResolvedRef::Inline(resolved) => *value = resolved,
+                    }
+                    return Ok(());
+                }
+                for v in map.values_mut() {
+                    self.resolve_value(location, v)?;
+                }
+            }
+            Value::Array(values) => {
+                for v in values.iter_mut() {
+                    self.resolve_value(location, v)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn resolve_ref(&mut self, location: &str, reference: &str) -> Result<ResolvedRef> {
+        let (document, pointer) = split_ref(reference);
+        let target = if document.is_empty() {
+            location.to_string()
+        } else {
+            self.sources[location].join(document).location()
+        };
+        let pointer = percent_decode(pointer);
+        let segments = pointer_segments(&pointer);
+
+        if target == self.root && self.is_root_component(&segments) {
+            return Ok(ResolvedRef::Keep(format!("#{}", pointer)));
+        }
+        // 3.1 的 $defs 引用相对于所在的 schema，在生成类型前统一提升
+        if target == self.root && segments.iter().any(|v| v.eq("$defs")) {
+            return Ok(ResolvedRef::Keep(reference.to_string()));
+        }
+        if let Some(name) = schema_name(&segments) {
+            let hoisted_name = self.hoist(&target, &pointer, &name)?;
+            let schema_ref = format!(
+                "#/{}/{}",
+                self.schemas_path().join("/"),
+                hoisted_name.replace('~', "~0").replace('/', "~1")
+            );
+            return Ok(ResolvedRef::Keep(schema_ref));
+        }
+
+        let key = format!("{}#{}", target, pointer);
+        if self.inlining.contains(&key) {
+            bail!(
+                "circular $ref {}: {} -> {}",
+                reference,
+                self.inlining.join(" -> "),
+                key
+            );
+        }
+        let mut value = self.find(&target, &pointer, reference)?;
+        self.inlining.push(key);
+        self.resolve_value(&target, &mut value)?;
+        self.inlining.pop();
+        Ok(ResolvedRef::Inline(value))
+    }
+
+    /// 判断是否是根文档中需要保留的引用
+    fn is_root_component(&self, segments: &[String]) -> bool {
+        match segments {
+            [section, _] if self.is_swagger_2 => {
+                ["definitions", "parameters", "responses"].contains(&section.as_str())
+            }
+            [components, section, _] if !self.is_swagger_2 => {
+                components.eq("components") && ["schemas", "parameters"].contains(&section.as_str())
+            }
+            _ => false,
+        }
+    }
+
+    /// 将外部文档中的 schema 提升到根文档中，返回提升后的名称
+    fn hoist(&mut self, target: &str, pointer: &str, name: &str) -> Result<String> {
+        let key = format!("{}#{}", target, pointer);
+        if let Some(hoisted_name) = self.hoisted.get(&key) {
+            return Ok(hoisted_name.clone());
+        }
+        let hoisted_name = self.unique_schema_name(target, name);
+        // 先记录名称，schema 之间的循环引用直接使用提升后的名称
+        self.hoisted.insert(key, hoisted_name.clone());
+        let mut schema = self.find(target, pointer, pointer)?;
+        self.resolve_value(target, &mut schema)?;
+        info!("hoist $ref {}#{} as {}", target, pointer, hoisted_name);
+        self.hoisted_schemas.push((hoisted_name.clone(), schema));
+        Ok(hoisted_name)
+    }
+
+    /// 生成不与根文档及其他已提升 schema 重名的名称
+    fn unique_schema_name(&self, target: &str, name: &str) -> String {
+        let root_schemas = self.documents[&self.root].pointer(&format!("/{}", self.schemas_path().join("/")));
+        let is_taken = |v: &str| {
+            root_schemas.and_then(|x| x.get(v)).is_some()
+                || self.hoisted.values().any(|hoisted_name| hoisted_name.eq(v))
+        };
+        if !is_taken(name) {
+            return name.to_string();
+        }
+        let prefixed_name = format!("{}{}", document_stem(target), name);
+        get_unique_name(&prefixed_name, &self.suffix_template, &is_taken)
+    }
+
+    fn find(&self, target: &str, pointer: &str, reference: &str) -> Result<Value> {
+        self.documents
+            .get(target)
+            .and_then(|document| document.pointer(pointer))
+            .cloned()
+            .ok_or_else(|| anyhow!("$ref {} not found in {}", reference, target))
+    }
+}
+
+/// 收集文档中所有的引用
+fn collect_refs(value: &Value, references: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get("$ref") {
+                references.push(reference.clone());
+            }
+            map.values().for_each(|v| collect_refs(v, references));
+        }
+        Value::Array(values) => values.iter().for_each(|v| collect_refs(v, references)),
+        _ => {}
+    }
+}
+
+/// 将引用拆分为文档地址和 json pointer
+///
+/// 如：./common.yaml#/components/schemas/Money
+/// 则返回 ("./common.yaml", "/components/schemas/Money")
+fn split_ref(reference: &str) -> (&str, &str) {
+    match reference.split_once('#') {
+        Some((document, pointer)) => (document, pointer),
+        None => (reference, ""),
+    }
+}
+
+/// 拆分 json pointer
+fn pointer_segments(pointer: &str) -> Vec<String> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|v| v.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+/// 判断引用是否指向一个 schema 定义，返回 schema 名称
+fn schema_name(segments: &[String]) -> Option<String> {
+    match segments {
+        [components, schemas, name] if components.eq("components") && schemas.eq("schemas") => {
+            Some(name.to_string())
+        }
+        [definitions, name] if definitions.eq("definitions") => Some(name.to_string()),
+        _ => None,
+    }
+}
+
+/// 获取文档名称，用作提升 schema 的前缀
+///
+/// 如：/path/to/common-types.yaml 则返回 CommonTypes
+fn document_stem(location: &str) -> String {
+    let file_name = location
+        .trim_end_matches('/')
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default();
+    let stem = file_name.split('.').next().unwrap_or_default();
+    stem.split(|c: char| !c.is_alphanumeric())
+        .map(capitalize)
+        .collect()
+}
+
+/// 获取对象中的字段，不存在时创建
+fn object_entry<'a>(value: &'a mut Value, key: &str) -> Result<&'a mut Value> {
+    value
+        .as_object_mut()
+        .map(|map| map.entry(key).or_insert_with(|| Value::Object(Map::new())))
+        .ok_or_else(|| anyhow!("{} parent is not an object", key))
+}
+
+/// 解码引用中的百分号编码
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' && index + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[index + 1..index + 3]).unwrap_or_default();
+            if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                decoded.push(byte);
+                index += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[index]);
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const ROOT: &str = "https://example.com/api/openapi.json";
+
+    /// 使用内存中的文档创建解析器，第一个文档为根文档
+    fn resolver(documents: Vec<(&str, Value)>) -> RefResolver {
+        let is_swagger_2 = documents[0].1.get("swagger").is_some();
+        RefResolver {
+            root: documents[0].0.to_string(),
+            sources: documents
+                .iter()
+                .map(|(location, _)| {
+                    (
+                        location.to_string(),
+                        ConfigSource::Http(location.to_string()),
+                    )
+                })
+                .collect(),
+            documents: documents
+                .into_iter()
+                .map(|(location, document)| (location.to_string(), document))
+                .collect(),
+            is_swagger_2,
+            hoisted: HashMap::new(),
+            hoisted_schemas: vec![],
+            inlining: vec![],
+            suffix_template: String::from("{name}{index}"),
+        }
+    }
+
+    #[test]
+    fn split_ref_test() {
+        assert_eq!(
+            split_ref("./common.yaml#/components/schemas/Money"),
+            ("./common.yaml", "/components/schemas/Money")
+        );
+        assert_eq!(
+            split_ref("#/components/schemas/User"),
+            ("", "/components/schemas/User")
+        );
+        assert_eq!(split_ref("./user.yaml"), ("./user.yaml", ""));
+    }
+
+    #[test]
+    fn pointer_segments_test() {
+        assert_eq!(
+            pointer_segments("/paths/~1users~1{id}/get"),
+            vec!["paths", "/users/{id}", "get"]
+        );
+        assert_eq!(pointer_segments("/a~0b"), vec!["a~b"]);
+        assert!(pointer_segments("").is_empty());
+    }
+
+    #[test]
+    fn percent_decode_test() {
+        assert_eq!(
+            percent_decode("/components/schemas/User%20Info"),
+            "/components/schemas/User Info"
+        );
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+
+    #[test]
+    fn document_stem_test() {
+        assert_eq!(document_stem("/path/to/common-types.yaml"), "CommonTypes");
+        assert_eq!(
+            document_stem("https://example.com/schemas/user.json"),
+            "User"
+        );
+        assert_eq!(document_stem("C:\\specs\\money.v1.yaml"), "Money");
+    }
+
+    #[test]
+    fn keep_root_component_refs() {
+        let document = resolver(vec![(
+            ROOT,
+            json!({
+                "openapi": "3.0.0",
+                "components": {
+                    "schemas": {
+                        "User": {
+                            "type": "object",
+                            "properties": { "friend": { "$ref": "#/components/schemas/User" } }
+                        }
+                    }
+                }
+            }),
+        )])
+        .resolve()
+        .unwrap();
+        assert_eq!(
+            document.pointer("/components/schemas/User/properties/friend/$ref"),
+            Some(&json!("#/components/schemas/User"))
+        );
+    }
+
+    #[test]
+    fn inline_non_schema_refs() {
+        let document = resolver(vec![
+            (
+                ROOT,
+                json!({
+                    "openapi": "3.0.0",
+                    "paths": { "/users": { "$ref": "./paths.json#/users" } }
+                }),
+            ),
+            (
+                "https://example.com/api/paths.json",
+                json!({ "users": { "get": { "operationId": "getUsers" } } }),
+            ),
+        ])
+        .resolve()
+        .unwrap();
+        assert_eq!(
+            document.pointer("/paths/~1users/get/operationId"),
+            Some(&json!("getUsers"))
+        );
+    }
+
+    #[test]
+    fn hoist_external_schemas() {
+        let document = resolver(vec![
+            (
+                ROOT,
+                json!({
+                    "openapi": "3.0.0",
+                    "components": {
+                        "schemas": {
+                            "Money": { "type": "string" },
+                            "Order": {
+                                "type": "object",
+                                "properties": {
+                                    "price": { "$ref": "../common/money.json#/components/schemas/Money" },
+                                    "user": { "$ref": "../common/money.json#/components/schemas/User" }
+                                }
+                            }
+                        }
+                    }
+                }),
+            ),
+            (
+                "https://example.com/common/money.json",
+                json!({
+                    "components": {
+                        "schemas": {
+                            "Money": { "type": "object", "properties": { "amount": { "type": "number" } } },
+                            "User": {
+                                "type": "object",
+                                "properties": { "parent": { "$ref": "#/components/schemas/User" } }
+                            }
+                        }
+                    }
+                }),
+            ),
+        ])
+        .resolve()
+        .unwrap();
+        let schemas = document.pointer("/components/schemas").unwrap();
+        // 与根文档重名时使用文档名作为前缀
+        assert_eq!(
+            schemas.pointer("/Order/properties/price/$ref"),
+            Some(&json!("#/components/schemas/MoneyMoney"))
+        );
+        assert_eq!(schemas.pointer("/MoneyMoney/type"), Some(&json!("object")));
+        assert_eq!(schemas.pointer("/Money/type"), Some(&json!("string")));
+        // schema 之间的循环引用使用提升后的名称
+        assert_eq!(
+            schemas.pointer("/User/properties/parent/$ref"),
+            Some(&json!("#/components/schemas/User"))
+        );
+    }
+
+    #[test]
+    fn hoist_external_schemas_with_suffix() {
+        let mut resolver = resolver(vec![
+            (
+                ROOT,
+                json!({
+                    "openapi": "3.0.0",
+                    "components": {
+                        "schemas": {
+                            "Money": { "type": "string" },
+                            "MoneyMoney": { "type": "string" },
+                            "Order": {
+                                "type": "object",
+                                "properties": {
+                                    "price": { "$ref": "../common/money.json#/components/schemas/Money" }
+                                }
+                            }
+                        }
+                    }
+                }),
+            ),
+            (
+                "https://example.com/common/money.json",
+                json!({
+                    "components": {
+                        "schemas": {
+                            "Money": { "type": "object", "properties": { "amount": { "type": "number" } } }
+                        }
+                    }
+                }),
+            ),
+        ]);
+        resolver.suffix_template = String::from("{name}_{index}");
+        let document = resolver.resolve().unwrap();
+        let schemas = document.pointer("/components/schemas").unwrap();
+        // 加上文档名前缀后仍然重名时使用后缀模板
+        assert_eq!(
+            schemas.pointer("/Order/properties/price/$ref"),
+            Some(&json!("#/components/schemas/MoneyMoney_2"))
+        );
+        assert_eq!(
+            schemas.pointer("/MoneyMoney_2/type"),
+            Some(&json!("object"))
+        );
+        assert_eq!(schemas.pointer("/MoneyMoney/type"), Some(&json!("string")));
+    }
+
+    #[test]
+    fn hoist_swagger_2_definitions() {
+        let document = resolver(vec![
+            (
+                ROOT,
+                json!({
+                    "swagger": "2.0",
+                    "definitions": {
+                        "Order": {
+                            "type": "object",
+                            "properties": { "user": { "$ref": "user.json#/definitions/User" } }
+                        }
+                    }
+                }),
+            ),
+            (
+                "https://example.com/api/user.json",
+                json!({ "definitions": { "User": { "type": "object" } } }),
+            ),
+        ])
+        .resolve()
+        .unwrap();
+        assert_eq!(
+            document.pointer("/definitions/Order/properties/user/$ref"),
+            Some(&json!("#/definitions/User"))
+        );
+        assert_eq!(
+            document.pointer("/definitions/User/type"),
+            Some(&json!("object"))
+        );
+    }
+
+    #[test]
+    fn detect_circular_inline_refs() {
+        let error = resolver(vec![(
+            ROOT,
+            json!({
+                "openapi": "3.0.0",
+                "x-a": { "next": { "$ref": "#/x-b" } },
+                "x-b": { "next": { "$ref": "#/x-a" } }
+            }),
+        )])
+        .resolve()
+        .unwrap_err();
+        assert!(error.to_string().starts_with("circular $ref"));
+    }
+
+    #[test]
+    fn missing_ref_is_an_error() {
+        let error = resolver(vec![(
+            ROOT,
+            json!({ "openapi": "3.0.0", "x-a": { "$ref": "#/x-missing" } }),
+        )])
+        .resolve()
+        .unwrap_err();
+        assert!(error.to_string().contains("not found"));
+    }
+}
@@ -0,0 +1,627 @@
+use std::collections::HashMap;
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::open_api_3::{
+    Open3ApiConfig, Open3Components, Open3ComponentsSchema, Open3Config, Open3Parameters,
+    Open3RequestBody, Open3Requests, Open3Response, Open3ResponseContent, Open3Schema,
+    Open3SchemaType, Open3Server, Open3Tag, merge_parameters, MergeParameter,
+};
+
+/// swagger 2.0 中 definitions 的引用前缀
+const SWAGGER_2_DEFINITIONS_REF: &str = "#/definitions/";
+/// open api 3 中 components.schemas 的引用前缀
+const OPEN_3_SCHEMAS_REF: &str = "#/components/schemas/";
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Swagger2Config {
+    // yaml 中 swagger: 2.0 会被解析为数字 所以同时接受数字与字符串
+    pub swagger: Value,
+
+    pub host: Option<String>,
+
+    #[serde(alias = "basePath")]
+    pub base_path: Option<String>,
+
+    pub schemes: Option<Vec<String>>,
+
+    pub consumes: Option<Vec<String>>,
+
+    pub produces: Option<Vec<String>>,
+
+    pub paths: HashMap<String, Swagger2Requests>,
+
+    pub definitions: Option<HashMap<String, Open3ComponentsSchema>>,
+
+    // 全局参数定义
+    pub parameters: Option<HashMap<String, Swagger2Parameters>>,
+
+    // 全局响应定义
+    pub responses: Option<HashMap<String, Swagger2Response>>,
+
+    pub tags: Option<Vec<Open3Tag>>,
+}
+
+type Swagger2ApiConfigOption = Option<Swagger2ApiConfig>;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Swagger2Requests {
+    pub get: Swagger2ApiConfigOption,
+    pub post: Swagger2ApiConfigOption,
+    pub put: Swagger2ApiConfigOption,
+    pub delete: Swagger2ApiConfigOption,
+    pub patch: Swagger2ApiConfigOption,
+    pub head: Swagger2ApiConfigOption,
+    pub options: Swagger2ApiConfigOption,
+
+    // 路径下所有请求共用的参数
+    pub parameters: Option<Vec<Swagger2Parameters>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Swagger2ApiConfig {
+    // 操作名称 缺少时使用请求方式与地址生成
+    #[serde(alias = "operationId", default)]
+    pub operation_id: String,
+
+    // 响应配置
+    pub responses: HashMap<String, Swagger2Response>,
+
+    pub summary: Option<String>,
+
+    // 缺少时放入默认模块
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    pub consumes: Option<Vec<String>>,
+
+    pub produces: Option<Vec<String>>,
+
+    pub parameters: Option<Vec<Swagger2Parameters>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Swagger2Parameters {
+    #[serde(alias = "$ref")]
+    pub parameter_ref: Option<String>,
+
+    #[serde(default)]
+    pub name: String,
+
+    #[serde(alias = "in", default)]
+    pub parameters_in: String,
+
+    pub description: Option<String>,
+
+    #[serde(default)]
+    pub required: bool,
+
+    // in: body 时的参数结构
+    pub schema: Option<Open3Schema>,
+
+    #[serde(alias = "type")]
+    pub parameter_type: Option<String>,
+
+    pub format: Option<String>,
+
+    pub items: Option<Box<Open3Schema>>,
+
+    #[serde(alias = "enum")]
+    pub parameter_enum: Option<Vec<Value>>,
+}
+
+impl MergeParameter for Swagger2Parameters {
+    fn parameter_key(&self) -> (&str, &str) {
+        (&self.name, &self.parameters_in)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Swagger2Response {
+    #[serde(alias = "$ref")]
+    pub response_ref: Option<String>,
+
+    // 响应描述
+    #[serde(default)]
+    pub description: String,
+
+    pub schema: Option<Open3Schema>,
+}
+
+impl Swagger2Config {
+    /// 将 swagger 2.0 文档升级为 open api 3 文档
+    ///
+    /// - definitions 转换为 components.schemas
+    /// - in: body 的参数转换为 requestBody
+    /// - in: formData 的参数合并为 multipart/form-data 的 requestBody
+    /// - host + basePath 转换为 servers
+    pub fn into_open_3(self) -> Open3Config {
+        let mut schemas = self.definitions.clone().unwrap_or_default();
+        for schema in schemas.values_mut() {
+            swagger_2_upgrade_schema(schema);
+        }
+
+        let mut paths = HashMap::new();
+        for (url, requests) in &self.paths {
+            paths.insert(
+                url.to_string(),
+                Open3Requests {
+                    get: self.upgrade_api_config(&requests.get, &requests.parameters),
+                    post: self.upgrade_api_config(&requests.post, &requests.parameters),
+                    put: self.upgrade_api_config(&requests.put, &requests.parameters),
+                    delete: self.upgrade_api_config(&requests.delete, &requests.parameters),
+                    patch: self.upgrade_api_config(&requests.patch, &requests.parameters),
+                    head: self.upgrade_api_config(&requests.head, &requests.parameters),
+                    options: self.upgrade_api_config(&requests.options, &requests.parameters),
+                    // swagger 2.0 不支持 trace
+                    trace: None,
+                    parameters: None,
+                },
+            );
+        }
+
+        Open3Config {
+            components: Open3Components {
+                schemas,
+                parameters: None,
+            },
+            paths,
+            tags: self.tags.clone().unwrap_or_default(),
+            servers: self.upgrade_servers(),
+        }
+    }
+
+    /// host 与 basePath 合并为 server 地址
+    fn upgrade_servers(&self) -> Option<Vec<Open3Server>> {
+        let base_path = self.base_path.clone().unwrap_or_default();
+        if let Some(host) = &self.host {
+            let schemes = self
+                .schemes
+                .clone()
+                .unwrap_or_else(|| vec![String::from("http")]);
+            return Some(
+                schemes
+                    .iter()
+                    .map(|scheme| Open3Server {
+                        url: format!("{scheme}://{host}{base_path}"),
+                        description: None,
+                    })
+                    .collect(),
+            );
+        }
+        if base_path.is_empty() {
+            return None;
+        }
+        Some(vec![Open3Server {
+            url: base_path,
+            description: None,
+        }])
+    }
+
+    fn upgrade_api_config(
+        &self,
+        api_config: &Swagger2ApiConfigOption,
+        path_parameters: &Option<Vec<Swagger2Parameters>>,
+    ) -> Option<Open3ApiConfig> {
+        let api_config = api_config.as_ref()?;
+        let consumes = api_config
+            .consumes
+            .as_ref()
+            .or(self.consumes.as_ref())
+            .cloned()
+            .unwrap_or_else(|| vec![String::from("application/json")]);
+        let produces = api_config
+            .produces
+            .as_ref()
+            .or(self.produces.as_ref())
+            .cloned()
+            .unwrap_or_else(|| vec![String::from("application/json")]);
+
+        let mut parameters = vec![];
+        let mut request_body = None;
+        let mut form_properties = HashMap::new();
+        let mut form_required = vec![];
+        for parameter in merge_parameters(path_parameters, &api_config.parameters, |v| {
+            self.resolve_parameter(v)
+        }) {
+            match parameter.parameters_in.as_str() {
+                "body" => {
+                    let mut schema = parameter.schema.clone().unwrap_or_default();
+                    swagger_2_upgrade_schema(&mut schema);
+                    request_body = Some(Open3RequestBody {
+                        content: consumes
+                            .iter()
+                            .map(|content_type| {
+                                (
+                                    content_type.to_string(),
+                                    Open3ResponseContent {
+                                        schema: schema.clone(),
+                                    },
+                                )
+                            })
+                            .collect(),
+                    });
+                }
+                "formData" => {
+                    form_properties.insert(
+                        parameter.name.clone(),
+                        swagger_2_parameter_schema(&parameter),
+                    );
+                    if parameter.required {
+                        form_required.push(parameter.name.clone());
+                    }
+                }
+                _ => parameters.push(Open3Parameters {
+                    name: parameter.name.clone(),
+                    parameters_in: parameter.parameters_in.clone(),
+                    description: parameter.description.clone(),
+                    required: parameter.required,
+                    schema: Some(swagger_2_parameter_schema(&parameter)),
+                    ..Default::default()
+                }),
+            }
+        }
+
+        // formData 参数合并为一个 object 类型的 requestBody
+        if !form_properties.is_empty() {
+            let content_type = if consumes
+                .iter()
+                .any(|v| v.eq("application/x-www-form-urlencoded"))
+            {
+                "application/x-www-form-urlencoded"
+            } else {
+                "multipart/form-data"
+            };
+            request_body = Some(Open3RequestBody {
+                content: HashMap::from([(
+                    content_type.to_string(),
+                    Open3ResponseContent {
+                        schema: Open3Schema {
+                            schema_type: Some("object".into()),
+                            properties: Some(form_properties),
+                            required: Some(form_required),
+                            ..Default::default()
+                        },
+                    },
+                )]),
+            });
+        }
+
+        let mut responses = HashMap::new();
+        for (code, response) in &api_config.responses {
+            let response = self.resolve_response(response);
+            let content = response.schema.as_ref().map(|schema| {
+                let mut schema = schema.clone();
+                swagger_2_upgrade_schema(&mut schema);
+                produces
+                    .iter()
+                    .map(|content_type| {
+                        (
+                            content_type.to_string(),
+                            Some(Open3ResponseContent {
+                                schema: schema.clone(),
+                            }),
+                        )
+                    })
+                    .collect()
+            });
+            responses.insert(
+                code.to_string(),
+                Open3Response {
+                    content,
+                    description: response.description.clone(),
+                },
+            );
+        }
+
+        Some(Open3ApiConfig {
+            operation_id: api_config.operation_id.clone(),
+            responses,
+            request_body,
+            summary: api_config.summary.clone(),
+            tags: api_config.tags.clone(),
+            parameters: if parameters.is_empty() {
+                None
+            } else {
+                Some(parameters)
+            },
+        })
+    }
+
+    /// 解析 #/parameters/xxx 形式的参数引用
+    fn resolve_parameter(&self, parameter: &Swagger2Parameters) -> Option<Swagger2Parameters> {
+        let Some(parameter_ref) = &parameter.parameter_ref else {
+            return Some(parameter.clone());
+        };
+        let name = parameter_ref.trim_start_matches("#/parameters/");
+        let resolved = self
+            .parameters
+            .as_ref()
+            .and_then(|parameters| parameters.get(name))
+            .cloned();
+        if resolved.is_none() {
+            warn!("swagger parameter {} not found", parameter_ref);
+        }
+        resolved
+    }
+
+    /// 解析 #/responses/xxx 形式的响应引用
+    fn resolve_response(&self, response: &Swagger2Response) -> Swagger2Response {
+        let Some(response_ref) = &response.response_ref else {
+            return response.clone();
+        };
+        let name = response_ref.trim_start_matches("#/responses/");
+        if let Some(resolved) = self
+            .responses
+            .as_ref()
+            .and_then(|responses| responses.get(name))
+        {
+            return resolved.clone();
+        }
+        warn!("swagger response {} not found", response_ref);
+        response.clone()
+    }
+}
+
+/// 非 body 参数的类型信息直接定义在参数上，需要转换成 schema
+fn swagger_2_parameter_schema(parameter: &Swagger2Parameters) -> Open3Schema {
+    let mut schema = Open3Schema {
+        schema_type: parameter
+            .parameter_type
+            .as_ref()
+            .map(|v| Open3SchemaType::Single(v.to_string())),
+        format: parameter.format.clone(),
+        items: parameter.items.clone(),
+        property_enum: parameter.parameter_enum.clone(),
+        description: parameter.description.clone(),
+        ..Default::default()
+    };
+    swagger_2_upgrade_schema(&mut schema);
+    schema
+}
+
+/// 将 swagger 2.0 的 schema 转换成 open api 3 的 schema
+///
+/// - #/definitions/xxx 引用转换为 #/components/schemas/xxx
+/// - file 类型转换为 string binary
+fn swagger_2_upgrade_schema(schema: &mut Open3Schema) {
+    schema.walk_mut(&mut |schema| {
+        if let Some(schema_ref) = &schema.schema_ref {
+            if let Some(name) = schema_ref.strip_prefix(SWAGGER_2_DEFINITIONS_REF) {
+                schema.schema_ref = Some(format!("{OPEN_3_SCHEMAS_REF}{name}"));
+            }
+        }
+        if schema.schema_type.as_ref().is_some_and(|v| v.contains("file")) {
+            schema.schema_type = Some("string".into());
+            schema.format = Some(String::from("binary"));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use log::{LevelFilter, Log, Metadata, Record};
+    use serde_json::json;
+
+    use super::*;
+
+    /// 记录 warn 日志，用于检查引用缺失时的警告
+    struct WarningLogger;
+
+    static WARNINGS: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+    impl Log for WarningLogger {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            metadata.level() <= log::Level::Warn
+        }
+
+        fn log(&self, record: &Record) {
+            if self.enabled(record.metadata()) {
+                WARNINGS.lock().unwrap().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: WarningLogger = WarningLogger;
+
+    fn has_warning(message: &str) -> bool {
+        WARNINGS.lock().unwrap().iter().any(|v| v.eq(message))
+    }
+
+    fn upgrade(document: Value) -> Open3Config {
+        serde_json::from_value::<Swagger2Config>(document)
+            .unwrap()
+            .into_open_3()
+    }
+
+    fn get_api_config<'a>(config: &'a Open3Config, url: &str) -> &'a Open3ApiConfig {
+        let requests = &config.paths[url];
+        requests.get.as_ref().or(requests.post.as_ref()).unwrap()
+    }
+
+    #[test]
+    fn body_to_request_body_test() {
+        let config = upgrade(json!({
+            "swagger": "2.0",
+            "paths": {
+                "/users": {
+                    "post": {
+                        "parameters": [
+                            { "name": "user", "in": "body", "schema": { "$ref": "#/definitions/User" } },
+                            { "name": "notify", "in": "query", "type": "boolean" }
+                        ],
+                        "responses": {
+                            "200": { "description": "ok", "schema": { "$ref": "#/definitions/User" } }
+                        }
+                    }
+                }
+            },
+            "definitions": {
+                "User": {
+                    "type": "object",
+                    "properties": { "parent": { "$ref": "#/definitions/User" } }
+                }
+            }
+        }));
+        let api_config = get_api_config(&config, "/users");
+        let request_body = api_config.request_body.as_ref().unwrap();
+        assert_eq!(
+            request_body.content["application/json"]
+                .schema
+                .schema_ref
+                .as_deref(),
+            Some("#/components/schemas/User")
+        );
+        // body 参数不再作为普通参数
+        let parameters = api_config.parameters.as_ref().unwrap();
+        assert_eq!(parameters.len(), 1);
+        assert_eq!(parameters[0].name, "notify");
+        assert_eq!(
+            parameters[0].get_schema().unwrap().schema_type,
+            Some("boolean".into())
+        );
+        let response = api_config.responses["200"].content.as_ref().unwrap();
+        assert_eq!(
+            response["application/json"]
+                .as_ref()
+                .unwrap()
+                .schema
+                .schema_ref
+                .as_deref(),
+            Some("#/components/schemas/User")
+        );
+        assert_eq!(
+            config.components.schemas["User"]
+                .properties
+                .as_ref()
+                .unwrap()["parent"]
+                .schema_ref
+                .as_deref(),
+            Some("#/components/schemas/User")
+        );
+    }
+
+    #[test]
+    fn form_data_to_request_body_test() {
+        let config = upgrade(json!({
+            "swagger": "2.0",
+            "paths": {
+                "/avatars": {
+                    "post": {
+                        "parameters": [
+                            { "name": "file", "in": "formData", "type": "file", "required": true },
+                            { "name": "name", "in": "formData", "type": "string" }
+                        ],
+                        "responses": { "200": { "description": "ok" } }
+                    }
+                },
+                "/login": {
+                    "post": {
+                        "consumes": ["application/x-www-form-urlencoded"],
+                        "parameters": [{ "name": "password", "in": "formData", "type": "string" }],
+                        "responses": { "200": { "description": "ok" } }
+                    }
+                }
+            }
+        }));
+        let request_body = get_api_config(&config, "/avatars")
+            .request_body
+            .as_ref()
+            .unwrap();
+        let schema = &request_body.content["multipart/form-data"].schema;
+        assert_eq!(schema.required, Some(vec![String::from("file")]));
+        // file 类型转换为 string binary
+        let file = &schema.properties.as_ref().unwrap()["file"];
+        assert_eq!(file.schema_type, Some("string".into()));
+        assert_eq!(file.format.as_deref(), Some("binary"));
+        assert!(get_api_config(&config, "/avatars").parameters.is_none());
+
+        let request_body = get_api_config(&config, "/login")
+            .request_body
+            .as_ref()
+            .unwrap();
+        assert!(request_body
+            .content
+            .contains_key("application/x-www-form-urlencoded"));
+        assert!(!request_body.content.contains_key("multipart/form-data"));
+    }
+
+    #[test]
+    fn parameter_and_response_refs_test() {
+        let _ = log::set_logger(&LOGGER).map(|_| log::set_max_level(LevelFilter::Warn));
+        let config = upgrade(json!({
+            "swagger": 2.0,
+            "paths": {
+                "/users/{id}": {
+                    "parameters": [{ "$ref": "#/parameters/UserId" }],
+                    "get": {
+                        "parameters": [{ "$ref": "#/parameters/Missing" }],
+                        "responses": {
+                            "200": { "$ref": "#/responses/UserResponse" },
+                            "404": { "$ref": "#/responses/MissingResponse" }
+                        }
+                    }
+                }
+            },
+            "parameters": {
+                "UserId": { "name": "id", "in": "path", "required": true, "type": "integer" }
+            },
+            "responses": {
+                "UserResponse": { "description": "user", "schema": { "type": "string" } }
+            }
+        }));
+        let api_config = get_api_config(&config, "/users/{id}");
+        // 缺失的参数引用被忽略
+        let parameters = api_config.parameters.as_ref().unwrap();
+        assert_eq!(parameters.len(), 1);
+        assert_eq!(parameters[0].name, "id");
+        assert_eq!(parameters[0].parameters_in, "path");
+        assert!(parameters[0].required);
+        assert_eq!(api_config.responses["200"].description, "user");
+        assert!(api_config.responses["200"].content.is_some());
+        assert!(api_config.responses["404"].content.is_none());
+        assert!(has_warning(
+            "swagger parameter #/parameters/Missing not found"
+        ));
+        assert!(has_warning(
+            "swagger response #/responses/MissingResponse not found"
+        ));
+    }
+
+    #[test]
+    fn servers_test() {
+        let servers = |document: Value| {
+            upgrade(document)
+                .servers
+                .unwrap_or_default()
+                .into_iter()
+                .map(|v| v.url)
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(
+            servers(json!({
+                "swagger": "2.0",
+                "host": "api.example.com",
+                "basePath": "/v1",
+                "schemes": ["https", "http"],
+                "paths": {}
+            })),
+            vec!["https://api.example.com/v1", "http://api.example.com/v1"]
+        );
+        assert_eq!(
+            servers(json!({ "swagger": "2.0", "host": "api.example.com", "paths": {} })),
+            vec!["http://api.example.com"]
+        );
+        assert_eq!(
+            servers(json!({ "swagger": "2.0", "basePath": "/v1", "paths": {} })),
+            vec!["/v1"]
+        );
+        assert!(servers(json!({ "swagger": "2.0", "paths": {} })).is_empty());
+    }
+}
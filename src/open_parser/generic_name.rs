@@ -0,0 +1,156 @@
+/// Springfox 风格的泛型 schema 名称
+///
+/// 如：Result«List«User»» 解析为 Result 以及类型参数 List«User»
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenericName {
+    pub name: String,
+    pub args: Vec<GenericName>,
+}
+
+impl GenericName {
+    /// 解析 schema 名称，名称不是合法的泛型格式时返回 None
+    pub fn parse(schema_name: &str) -> Option<GenericName> {
+        let chars: Vec<char> = schema_name.chars().collect();
+        let mut index = 0;
+        let generic_name = GenericName::parse_name(&chars, &mut index)?;
+        if index == chars.len() {
+            Some(generic_name)
+        } else {
+            None
+        }
+    }
+
+    fn parse_name(chars: &[char], index: &mut usize) -> Option<GenericName> {
+        let mut name = String::new();
+        while let Some(c) = chars.get(*index).filter(|c| !['«', '»', ','].contains(c)) {
+            name.push(*c);
+            *index += 1;
+        }
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return None;
+        }
+        let mut args = vec![];
+        if chars.get(*index) == Some(&'«') {
+            *index += 1;
+            loop {
+                args.push(GenericName::parse_name(chars, index)?);
+                match chars.get(*index) {
+                    Some(',') => *index += 1,
+                    Some('»') => {
+                        *index += 1;
+                        break;
+                    }
+                    _ => return None,
+                }
+            }
+        }
+        Some(GenericName { name, args })
+    }
+
+    pub fn is_generic(&self) -> bool {
+        !self.args.is_empty()
+    }
+
+    /// 还原为 schema 名称，如：Result«List«User»»
+    pub fn schema_name(&self) -> String {
+        if self.args.is_empty() {
+            return self.name.clone();
+        }
+        let args: Vec<String> = self.args.iter().map(|v| v.schema_name()).collect();
+        format!("{}«{}»", self.name, args.join(","))
+    }
+
+    /// 所有类型参数（包括嵌套的类型参数）的 schema 名称
+    pub fn arg_schema_names(&self) -> Vec<String> {
+        self.args
+            .iter()
+            .flat_map(|v| {
+                let mut names = vec![v.schema_name()];
+                names.extend(v.arg_schema_names());
+                names
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generic_name(name: &str, args: Vec<GenericName>) -> GenericName {
+        GenericName {
+            name: name.to_string(),
+            args,
+        }
+    }
+
+    #[test]
+    fn parse_plain_name() {
+        let name = GenericName::parse("User").unwrap();
+        assert_eq!(name, generic_name("User", vec![]));
+        assert!(!name.is_generic());
+    }
+
+    #[test]
+    fn parse_nested_generic_name() {
+        let name = GenericName::parse("Result«Map«string,List«User»»»").unwrap();
+        assert_eq!(
+            name,
+            generic_name(
+                "Result",
+                vec![generic_name(
+                    "Map",
+                    vec![
+                        generic_name("string", vec![]),
+                        generic_name("List", vec![generic_name("User", vec![])]),
+                    ],
+                )],
+            )
+        );
+        assert!(name.is_generic());
+    }
+
+    #[test]
+    fn parse_trims_whitespace() {
+        assert_eq!(
+            GenericName::parse("Map« string , User »").unwrap(),
+            generic_name(
+                "Map",
+                vec![generic_name("string", vec![]), generic_name("User", vec![])],
+            )
+        );
+    }
+
+    #[test]
+    fn parse_invalid_name() {
+        for name in [
+            "",
+            "Result«",
+            "Result«User",
+            "Result«User»»",
+            "«User»",
+            "Result«User,»",
+            "A,B",
+        ] {
+            assert_eq!(GenericName::parse(name), None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn schema_name_round_trip() {
+        for name in ["User", "Result«User»", "Result«Map«string,List«User»»»"] {
+            assert_eq!(GenericName::parse(name).unwrap().schema_name(), name);
+        }
+    }
+
+    #[test]
+    fn arg_schema_names() {
+        assert_eq!(
+            GenericName::parse("Result«Page«User»»")
+                .unwrap()
+                .arg_schema_names(),
+            vec!["Page«User»", "User"]
+        );
+    }
+}
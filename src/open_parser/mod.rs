@@ -1,2 +1,3 @@
+pub mod generic_name;
 pub mod open_api_javascript;
 pub mod parser_tools;
\ No newline at end of file
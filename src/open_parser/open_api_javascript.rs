@@ -1,15 +1,27 @@
-use super::parser_tools::{OpenApiModule, OpenApiRequester};
+use super::{
+    generic_name::GenericName,
+    parser_tools::{OpenApiModule, OpenApiParameter, OpenApiRequester},
+};
 use crate::{
-    command_config::CommandConfig,
+    command_config::{CommandConfig, EnumStyle, InlineObjectStyle, ModuleNaming, MultiTagStyle},
     open_api::open_api_3::{
-        Open3ApiConfig, Open3Components, Open3ComponentsSchema, Open3Config, Open3Requests,
-        Open3Schema,
+        Open3AdditionalProperties, Open3ApiConfig, Open3Components, Open3ComponentsSchema, Open3Config, Open3Discriminator,
+        Open3Parameters, Open3Requests, Open3Schema, merge_parameters,
+    },
+    tools::{
+        module_name::{sanitize_file_name, to_camel_case, to_kebab_case},
+        tools::{capitalize, get_unique_name},
+        ts_escape::{
+            escape_doc_comment, join_identifier_words, ts_identifier, ts_string_literal, ts_type_identifier,
+        },
+        type_mapping::TypeMapping,
     },
-    tools::tools::capitalize,
 };
 use lazy_static::lazy_static;
+use log::warn;
 use regex::Regex;
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 pub trait OpenApiJavaScriptParser {
     /// 获取模块列表
@@ -21,13 +33,31 @@ pub trait OpenApiJavaScriptParser {
 
     /// 获取所有类型列表
     fn get_interface_enum_list(&mut self, ignore_option: &bool) -> Vec<String>;
+
+    /// 获取 discriminator 联合类型的类型守卫函数列表
+    fn get_type_guard_list(&self) -> Vec<String>;
+
+    /// 获取运行时的枚举列表（enum 或 as const 对象）
+    fn get_enum_list(&self) -> Vec<String>;
+
+    /// 获取响应值转换函数列表
+    fn get_converter_list(&self) -> Vec<String>;
 }
 
+/// 参数全部放在请求参数类型中的请求方式
+const QUERY_METHODS: [&str; 5] = ["get", "delete", "head", "options", "trace"];
+
+/// 模块名称与接口的对应列表
+type ApiList = Vec<(String, OpenApiRequester)>;
+
 pub struct OpenApi3JavaScript<'a, 'b> {
     config: &'a mut Open3Config,
     command_config: &'b CommandConfig,
     api_list: Vec<(String, OpenApiRequester)>,
     all_api_list: Vec<(String, OpenApiRequester)>,
+    module_list: Vec<OpenApiModule>,
+    // 需要生成的 schema，为 None 时生成全部
+    reachable_schemas: Option<HashSet<String>>,
 }
 
 impl<'a, 'b> OpenApi3JavaScript<'a, 'b> {
@@ -35,34 +65,59 @@ impl<'a, 'b> OpenApi3JavaScript<'a, 'b> {
         config: &'a mut Open3Config,
         command_config: &'b CommandConfig,
     ) -> OpenApi3JavaScript<'a, 'b> {
+        open_3_fill_operation_names(config, command_config);
+        open_3_hoist_defs(config, &command_config.name_collision_suffix);
+        open_3_apply_type_mapping(config, &command_config.type_mapping);
+        open_3_apply_generics(
+            config,
+            command_config.flatten_generics,
+            &command_config.name_collision_suffix,
+        );
+        open_3_resolve_type_name_collisions(config, &command_config.name_collision_suffix);
+        // 先提升匿名对象，拆分请求体与响应的 schema 时引用同一个提升后的 schema
+        if command_config.inline_object == InlineObjectStyle::Hoist {
+            open_3_hoist_inline_objects(
+                &mut config.components,
+                &command_config.name_collision_suffix,
+            );
+        }
+        open_3_split_read_write_schemas(config, &command_config.name_collision_suffix);
         let (api_list, all_api_list) = open_3_get_api_list(config, command_config);
+        let module_list = open_3_get_module_list(config, &api_list, command_config);
+        // 生成的请求类型中的匿名对象
+        if command_config.inline_object == InlineObjectStyle::Hoist {
+            open_3_hoist_inline_objects(
+                &mut config.components,
+                &command_config.name_collision_suffix,
+            );
+        }
+        let reachable_schemas = if command_config.tags.is_empty() || command_config.all_schemas {
+            None
+        } else {
+            Some(open_3_get_reachable_schemas(config, &api_list, command_config))
+        };
         OpenApi3JavaScript {
             config,
             api_list,
             command_config,
             all_api_list,
+            module_list,
+            reachable_schemas,
+        }
+    }
+
+    /// 判断 schema 是否需要生成
+    fn is_schema_reachable(&self, schema_name: &str) -> bool {
+        match &self.reachable_schemas {
+            Some(reachable_schemas) => reachable_schemas.contains(schema_name),
+            None => true,
         }
     }
 }
 
 impl OpenApiJavaScriptParser for OpenApi3JavaScript<'_, '_> {
     fn get_module_list(&self) -> Vec<OpenApiModule> {
-        self.config
-            .tags
-            .iter()
-            .filter_map(|v| {
-                let name = v.name.to_string();
-                if !self.command_config.tags.is_empty() && !self.command_config.tags.contains(&name)
-                {
-                    None
-                } else {
-                    Some(OpenApiModule {
-                        description: v.description.to_string(),
-                        name: v.name.to_string(),
-                    })
-                }
-            })
-            .collect()
+        self.module_list.clone()
     }
 
     fn get_api_list(&self) -> &Vec<(String, OpenApiRequester)> {
@@ -81,15 +136,22 @@ impl OpenApiJavaScriptParser for OpenApi3JavaScript<'_, '_> {
         }
         let mut components_schema_vec: Vec<(&String, &Open3ComponentsSchema)> =
             self.config.components.schemas.iter().collect();
-        components_schema_vec.sort_by(|a, b| a.0.cmp(&b.0));
+        components_schema_vec.sort_by(|a, b| a.0.cmp(b.0));
 
         let request_scheme_name_vec: Vec<String> = self
             .all_api_list
             .iter()
-            .map(|v| v.1.request_schema_name.to_string())
+            .flat_map(|v| {
+                std::iter::once(v.1.request_schema_name.to_string())
+                    .chain(v.1.parameters_schema_name.clone())
+            })
             .collect();
 
-        for (_, schema) in components_schema_vec {
+        for (key, schema) in components_schema_vec {
+            // 泛型实例使用泛型声明
+            if schema.generic_instance || !self.is_schema_reachable(key) {
+                continue;
+            }
             str_vec.push(open_3_create_ts_interface_enum(
                 schema,
                 &request_scheme_name_vec,
@@ -99,6 +161,139 @@ impl OpenApiJavaScriptParser for OpenApi3JavaScript<'_, '_> {
         }
         str_vec
     }
+
+    fn get_enum_list(&self) -> Vec<String> {
+        if self.command_config.enum_style == EnumStyle::Union {
+            return vec![];
+        }
+        let mut components_schema_vec: Vec<(&String, &Open3ComponentsSchema)> =
+            self.config.components.schemas.iter().collect();
+        components_schema_vec.sort_by(|a, b| a.0.cmp(b.0));
+        let mut enum_vec = vec![];
+        let mut enum_name_vec: Vec<String> = vec![];
+        for (key, schema) in components_schema_vec {
+            if !self.is_schema_reachable(key) {
+                continue;
+            }
+            let type_name =
+                open_3_get_type_name_from_schema_ref(schema.title.as_deref().unwrap_or(key));
+            if let Some(enum_str) =
+                open_3_create_enum(&type_name, schema, self.command_config.enum_style)
+            {
+                enum_vec.push(enum_str);
+            }
+            // 属性中的匿名枚举 使用 类型名称+属性名称 命名
+            let mut properties: Vec<(&String, &Open3Schema)> =
+                schema.properties.iter().flatten().collect();
+            properties.sort_by(|a, b| a.0.cmp(b.0));
+            for (property_name, property) in properties {
+                let property = property.items.as_deref().unwrap_or(property);
+                if property.property_enum.as_ref().is_none_or(|v| v.is_empty()) {
+                    continue;
+                }
+                let enum_name = get_unique_name(
+                    &format!(
+                        "{}{}",
+                        type_name,
+                        capitalize(&open_3_get_enum_member_name(property_name))
+                    ),
+                    &self.command_config.name_collision_suffix,
+                    &|v| {
+                        enum_name_vec.iter().any(|x| x.eq(v))
+                            || open_3_type_name_is_taken(&self.config.components, v)
+                    },
+                );
+                enum_name_vec.push(enum_name.clone());
+                if let Some(enum_str) =
+                    open_3_create_enum(&enum_name, property, self.command_config.enum_style)
+                {
+                    enum_vec.push(enum_str);
+                }
+            }
+        }
+        enum_vec
+    }
+
+    /// 只生成响应中用到的转换函数
+    fn get_converter_list(&self) -> Vec<String> {
+        lazy_static! {
+            static ref CONVERTER_NAME_REGEX: Regex = Regex::new(r"\b(convert\w+)\(").unwrap();
+        }
+        if self.command_config.wrap.is_some() {
+            return vec![];
+        }
+        let components = &self.config.components;
+        let converted =
+            open_3_get_converted_schemas(components, &self.command_config.name_collision_suffix);
+        let converter_schema_map: HashMap<&String, &String> =
+            converted.iter().map(|v| (v.1, v.0)).collect();
+        // 只生成部分 schema 时 只使用选中的接口
+        let api_list = if self.reachable_schemas.is_some() {
+            &self.api_list
+        } else {
+            &self.all_api_list
+        };
+        let mut pending: Vec<String> = api_list
+            .iter()
+            .filter_map(|v| v.1.response_converter.as_ref())
+            .flat_map(|v| CONVERTER_NAME_REGEX.captures_iter(v))
+            .map(|v| v[1].to_string())
+            .collect();
+        let mut converter_map: HashMap<String, String> = HashMap::new();
+        while let Some(converter_name) = pending.pop() {
+            if converter_map.contains_key(&converter_name) {
+                continue;
+            }
+            let Some(schema) = converter_schema_map
+                .get(&converter_name)
+                .and_then(|v| components.schemas.get(*v))
+            else {
+                continue;
+            };
+            let Some(expression) = open_3_get_convert_expression(schema, "value", &converted, "")
+            else {
+                continue;
+            };
+            pending.extend(
+                CONVERTER_NAME_REGEX
+                    .captures_iter(&expression)
+                    .map(|v| v[1].to_string()),
+            );
+            converter_map.insert(
+                converter_name.clone(),
+                format!(
+                    r#"
+export function {converter_name}(value: any): any {{
+    return {expression};
+}}
+"#
+                ),
+            );
+        }
+        let mut converter_vec: Vec<(String, String)> = converter_map.into_iter().collect();
+        converter_vec.sort_by(|a, b| a.0.cmp(&b.0));
+        converter_vec.into_iter().map(|v| v.1).collect()
+    }
+
+    fn get_type_guard_list(&self) -> Vec<String> {
+        let mut components_schema_vec: Vec<(&String, &Open3ComponentsSchema)> =
+            self.config.components.schemas.iter().collect();
+        components_schema_vec.sort_by(|a, b| a.0.cmp(b.0));
+        let mut guard_name_vec: Vec<String> = vec![];
+        components_schema_vec
+            .into_iter()
+            .filter(|(key, _)| self.is_schema_reachable(key))
+            .filter_map(|(key, schema)| {
+                open_3_create_type_guard(
+                    key,
+                    schema,
+                    &self.command_config.namespace,
+                    &self.command_config.name_collision_suffix,
+                    &mut guard_name_vec,
+                )
+            })
+            .collect()
+    }
 }
 
 /// 将open api类型转成ts对应的类型
@@ -123,6 +318,7 @@ pub fn ts_type_transform(data_type: &str) -> String {
             m.insert("File", "any");
             m.insert("string", "string");
             m.insert("boolean", "boolean");
+            m.insert("null", "null");
             m
         };
     }
@@ -133,204 +329,1659 @@ pub fn ts_type_transform(data_type: &str) -> String {
     }
 }
 
-/// 处理OpenApi3的类型
-///
-/// 转换成易处理的 OpenApiRequester 类型
+/// 将 3.1 schema 中的 $defs 提升到 components 中
 ///
-/// 目前仅支持get post delete put
-fn open_3_get_api_list(
-    config: &mut Open3Config,
-    command_config: &CommandConfig,
-) -> (
-    Vec<(String, OpenApiRequester)>,
-    Vec<(String, OpenApiRequester)>,
-) {
-    let mut paths_vec: Vec<(&String, &Open3Requests)> = config.paths.iter().collect();
-    paths_vec.sort_by(|a, b| a.0.cmp(b.0));
-    let mut api_list: Vec<(String, OpenApiRequester)> = vec![];
-    let mut all_api_list: Vec<(String, OpenApiRequester)> = vec![];
-    for (url, requests) in paths_vec {
-        for (method, request) in requests.iter() {
-            if let Some(api_config) = request {
-                let module: &String = &api_config.tags[0];
-                let operation_id = &api_config.operation_id;
-                let request_type = open_3_get_request_type_name(
-                    &method,
-                    &mut config.components,
-                    &api_config,
-                    command_config,
-                );
-                let open_api_requester = OpenApiRequester {
-                    summary: if let Some(summary) = &api_config.summary {
-                        summary.to_string()
-                    } else {
-                        String::new()
-                    },
-                    method: method.clone(),
-                    operation_id: format!(
-                        "{}{}",
-                        command_config
-                            .operation_prefix
-                            .as_ref()
-                            .unwrap_or(&"".to_string()),
-                        operation_id.to_string()
-                    ),
-                    url: url.to_string(),
-                    request_schema_name: request_type.0,
-                    // 可作为调用方法的参数类型， 已经处理是否可选
-                    request_type_name: request_type.1,
-                    // content_type: String::from("application/json"),
-                    response_type_name: open_3_get_response_type_name(&api_config, command_config),
-                    is_form: api_config.parameters.is_some() && method.eq("post"),
-                };
-                all_api_list.push((module.to_string(), open_api_requester.clone()));
-                // 如果已经指定了tag， 则忽略其他tag
-                if !command_config.tags.is_empty() {
-                    if !command_config.tags.contains(&module) {
-                        continue;
-                    }
+/// $defs 的引用（如 #/components/schemas/User/$defs/Address）按最后一段名称查找类型，
+/// 提升后即可与普通的 components schema 一样生成类型，
+/// 名称已被使用时按照后缀模板重命名，并将引用改为指向重命名后的 schema
+fn open_3_hoist_defs(config: &mut Open3Config, suffix_template: &str) {
+    let components = &mut config.components;
+    let mut schema_names: Vec<String> = components.schemas.keys().cloned().collect();
+    schema_names.sort();
+    // (所属的 components schema 名称, $defs 名称, $defs schema)
+    let mut defs_vec: Vec<(String, String, Open3Schema)> = vec![];
+    for schema_name in schema_names {
+        if let Some(schema) = components.schemas.get_mut(&schema_name) {
+            schema.walk_mut(&mut |v| {
+                if let Some(defs) = v.defs.take() {
+                    let mut defs: Vec<(String, Open3Schema)> = defs.into_iter().collect();
+                    defs.sort_by(|a, b| a.0.cmp(&b.0));
+                    defs_vec.extend(defs.into_iter().map(|(k, v)| (schema_name.clone(), k, v)));
                 }
-                api_list.push((module.to_string(), open_api_requester));
+            });
+        }
+    }
+    let mut rename_vec: Vec<(String, String, String)> = vec![];
+    while let Some((owner_name, def_name, mut def_schema)) = defs_vec.pop() {
+        // $defs 中可能还有嵌套的 $defs
+        def_schema.walk_mut(&mut |v| {
+            if let Some(defs) = v.defs.take() {
+                defs_vec.extend(defs.into_iter().map(|(k, v)| (owner_name.clone(), k, v)));
             }
+        });
+        let type_name = ts_type_identifier(&def_name);
+        let unique_name = get_unique_name(&type_name, suffix_template, &|name| {
+            open_3_type_name_is_taken(components, name)
+        });
+        if !unique_name.eq(&type_name) {
+            warn!(
+                "$defs {} of {} already exists in components, rename to {}",
+                def_name, owner_name, unique_name
+            );
+        }
+        if !unique_name.eq(&def_name) {
+            rename_vec.push((owner_name, def_name, unique_name.clone()));
         }
+        components.schemas.insert(unique_name, def_schema);
+    }
+    if rename_vec.is_empty() {
+        return;
+    }
+    for schema in open_3_get_config_schemas_mut(config) {
+        schema.walk_mut(&mut |v| {
+            let Some(schema_ref) = &v.schema_ref else {
+                return;
+            };
+            let renamed = rename_vec.iter().find(|(owner_name, def_name, _)| {
+                schema_ref.starts_with(&format!("#/components/schemas/{}/", owner_name))
+                    && schema_ref.ends_with(&format!("/$defs/{}", def_name))
+            });
+            if let Some((_, _, unique_name)) = renamed {
+                v.schema_ref = Some(format!("#/components/schemas/{}", unique_name));
+            }
+        });
     }
-    (api_list, all_api_list)
 }
 
-/// 获取响应类型名称
-fn open_3_get_response_type_name(
-    api_config: &Open3ApiConfig,
-    command_config: &CommandConfig,
-) -> String {
-    let mut res_type = None;
-    if let Some(response_content_map) = api_config
-        .responses
-        .get("200")
-        .and_then(|x| x.content.as_ref())
-    {
-        for (_, response_content) in response_content_map {
-            if let Some(response_content) = response_content {
-                let schema = &response_content.schema;
-                res_type = Some(open_3_get_type_name_from_schema(
-                    schema,
-                    command_config.namespace.clone(),
-                    "",
-                ));
+/// 补全缺少 operationId 或 tags 的接口
+///
+/// 缺少 operationId 时使用请求方式与地址生成名称，如：GET /users/{id} 则为 getUsersById，
+/// 缺少 tags 时放入 --default-module 指定的模块。
+/// 加上 --operation-prefix 并转换为标识符后重名时（如：get-users 与 GET /users），
+/// 按照 --name-collision-suffix 重命名
+fn open_3_fill_operation_names(config: &mut Open3Config, command_config: &CommandConfig) {
+    let operation_prefix = command_config.operation_prefix.as_deref().unwrap_or_default();
+    let get_function_name = |v: &str| ts_identifier(&format!("{operation_prefix}{v}"));
+    let default_module = &command_config.default_module;
+    let mut paths_vec: Vec<(&String, &mut Open3Requests)> = config.paths.iter_mut().collect();
+    paths_vec.sort_by(|a, b| a.0.cmp(b.0));
+    let mut operations: Vec<(&str, &String, &mut Open3ApiConfig)> = vec![];
+    for (url, requests) in paths_vec {
+        for (method, api_config) in [
+            ("get", &mut requests.get),
+            ("post", &mut requests.post),
+            ("put", &mut requests.put),
+            ("delete", &mut requests.delete),
+            ("patch", &mut requests.patch),
+            ("head", &mut requests.head),
+            ("options", &mut requests.options),
+            ("trace", &mut requests.trace),
+        ] {
+            if let Some(api_config) = api_config {
+                operations.push((method, url, api_config));
             }
         }
     }
-    // 支持传入一个包装层 对response类型进行包装
-    if let Some(wrap) = &command_config.wrap {
-        if let Some(res_t) = res_type {
-            res_type = Some(format!("{wrap}<{res_t}>"));
+    let mut function_names: HashSet<String> = HashSet::new();
+    for (method, url, api_config) in operations.iter_mut() {
+        if api_config.operation_id.trim().is_empty() {
+            continue;
+        }
+        let operation_id = get_unique_name(
+            &api_config.operation_id,
+            &command_config.name_collision_suffix,
+            &|v| function_names.contains(&get_function_name(v)),
+        );
+        if !operation_id.eq(&api_config.operation_id) {
+            warn!(
+                "{} {} operationId {} conflicts with another operation, use {}",
+                method.to_uppercase(),
+                url,
+                api_config.operation_id,
+                operation_id
+            );
+            api_config.operation_id = operation_id;
+        }
+        function_names.insert(get_function_name(&api_config.operation_id));
+    }
+    for (method, url, api_config) in operations {
+        if api_config.operation_id.trim().is_empty() {
+            let operation_id = get_unique_name(
+                &open_3_get_operation_name(method, url),
+                &command_config.name_collision_suffix,
+                &|v| function_names.contains(&get_function_name(v)),
+            );
+            warn!(
+                "{} {} has no operationId, use {}",
+                method.to_uppercase(),
+                url,
+                operation_id
+            );
+            function_names.insert(get_function_name(&operation_id));
+            api_config.operation_id = operation_id;
+        }
+        if api_config.tags.is_empty() {
+            warn!(
+                "{} has no tags, put it into module {}",
+                api_config.operation_id, default_module
+            );
+            api_config.tags.push(default_module.clone());
         }
     }
-    res_type.get_or_insert("void".into()).clone()
 }
 
-/// 获取请求参数类型名称
-///
-/// 如若当前的请求参数类型是可选的 则会在类型后面拼接 | void，
-/// 将拼接结果作为第二个参数返回
+/// 使用请求方式与地址生成接口名称
 ///
-/// 如果命令行参数指定了namespace 则会将namespace拼接在类型前
+/// 地址参数使用 By 连接，如：GET /users/{id}/posts 则返回 getUsersByIdPosts
+fn open_3_get_operation_name(method: &str, url: &str) -> String {
+    let mut operation_name = method.to_string();
+    for segment in url.split('/').filter(|v| !v.is_empty()) {
+        if let Some(parameter) = segment.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+            operation_name.push_str("By");
+            operation_name.push_str(&capitalize(&join_identifier_words(parameter)));
+        } else {
+            operation_name.push_str(&capitalize(&join_identifier_words(segment)));
+        }
+    }
+    operation_name
+}
+
+/// 获取文档中所有的顶层 schema
 ///
-/// 对于get/delete请求
+/// 包括 components 中的 schema、参数以及请求体和响应中的 schema
+fn open_3_get_config_schemas_mut(config: &mut Open3Config) -> Vec<&mut Open3Schema> {
+    let mut schemas: Vec<&mut Open3Schema> = config.components.schemas.values_mut().collect();
+    let mut parameters: Vec<&mut Open3Parameters> =
+        config.components.parameters.iter_mut().flatten().map(|v| v.1).collect();
+    for requests in config.paths.values_mut() {
+        parameters.extend(requests.parameters.iter_mut().flatten());
+        for api_config in [
+            &mut requests.get,
+            &mut requests.post,
+            &mut requests.put,
+            &mut requests.delete,
+            &mut requests.patch,
+            &mut requests.head,
+            &mut requests.options,
+            &mut requests.trace,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            parameters.extend(api_config.parameters.iter_mut().flatten());
+            if let Some(request_body) = &mut api_config.request_body {
+                schemas.extend(request_body.content.values_mut().map(|v| &mut v.schema));
+            }
+            for response in api_config.responses.values_mut() {
+                schemas.extend(
+                    response
+                        .content
+                        .iter_mut()
+                        .flat_map(|v| v.values_mut())
+                        .flatten()
+                        .map(|v| &mut v.schema),
+                );
+            }
+        }
+    }
+    for parameter in parameters {
+        schemas.extend(parameter.schema.as_mut());
+        schemas.extend(parameter.content.iter_mut().flat_map(|v| v.values_mut()).map(|v| &mut v.schema));
+    }
+    schemas
+}
+
+/// 根据用户的类型映射 标记 schema 对应的 ts 类型以及响应值的转换表达式
 ///
-/// - 根据api_config判断当前请求类型
-/// - 如果使用path或query参数
-/// - 将path和query参数混合生成新的类型塞进components（可使用operation_id + 关键字作为地址参数的类型名称）
-fn open_3_get_request_type_name(
-    method: &str,
-    components: &mut Open3Components,
-    api_config: &Open3ApiConfig,
-    command_config: &CommandConfig,
-) -> (String, String) {
-    if vec!["get", "delete"].contains(&method) {
-        generate_get_request_type(components, api_config, command_config)
-    } else {
-        generate_post_request_type(components, api_config, command_config)
+/// 优先使用 type/format 查找，找不到时使用 type 查找，
+/// 类型数组中除 null 外只有一个类型时才会映射，如：["string", "null"] 映射为 Date | null
+fn open_3_apply_type_mapping(config: &mut Open3Config, type_mapping: &HashMap<String, TypeMapping>) {
+    if type_mapping.is_empty() {
+        return;
+    }
+    for schema in open_3_get_config_schemas_mut(config) {
+        schema.walk_mut(&mut |v| {
+            if v.schema_ref.is_some()
+                || v.property_enum.is_some()
+                || v.const_value.is_some()
+                || v.properties.is_some()
+                || v.is_composition()
+            {
+                return;
+            }
+            let Some(schema_type) = &v.schema_type else {
+                return;
+            };
+            let types: Vec<&str> = schema_type.types().into_iter().filter(|x| !x.eq(&"null")).collect();
+            let [data_type] = types[..] else {
+                return;
+            };
+            let mapping = v
+                .format
+                .as_ref()
+                .and_then(|format| type_mapping.get(&format!("{}/{}", data_type, format)))
+                .or_else(|| type_mapping.get(data_type));
+            if let Some(mapping) = mapping {
+                v.ts_type = Some(if schema_type.contains("null") {
+                    format!("{} | null", mapping.ts_type())
+                } else {
+                    mapping.ts_type().to_string()
+                });
+                v.ts_convert = mapping.convert();
+            }
+        });
     }
 }
 
-/// 生成get请求的请求类型并塞进components
+/// 处理 Springfox 风格的泛型 schema，如：Result«User»、Result«List«User»»
 ///
-/// 类型被统一成了string
-fn generate_get_request_type(
-    components: &mut Open3Components,
-    api_config: &Open3ApiConfig,
-    command_config: &CommandConfig,
-) -> (String, String) {
-    let schemas = &mut components.schemas;
-    let mut properties = HashMap::new();
-    let type_name = format!("{}Query", capitalize(&api_config.operation_id));
-    let type_name_with_namespace = if let Some(namespace) = &command_config.namespace {
-        format!("{}.{}", namespace, type_name)
-    } else {
-        type_name.clone()
-    };
-    let mut required_vec = Vec::new();
-    if let Some(parameters) = &api_config.parameters {
-        parameters.iter().for_each(|v| {
+/// 同一泛型的所有实例合并生成一个泛型声明（如 Result<T>），实例在使用处生成 Result<User>，
+/// 实例本身不再生成类型声明。
+/// 以下情况将名称中的 «», 去掉生成普通类型（如 ResultUser）：
+///
+/// - 指定了 --flatten-generics
+/// - 泛型名称与已有的 schema 重名、实例的类型参数数量不一致或实例不是对象
+fn open_3_apply_generics(config: &mut Open3Config, flatten_generics: bool, suffix_template: &str) {
+    let components = &mut config.components;
+    let mut instance_names: Vec<String> = components
+        .schemas
+        .keys()
+        .filter(|v| v.contains('«'))
+        .cloned()
+        .collect();
+    if instance_names.is_empty() {
+        return;
+    }
+    instance_names.sort();
+
+    let mut generic_map: BTreeMap<String, Vec<(String, GenericName)>> = BTreeMap::new();
+    let mut flattened_names: Vec<String> = vec![];
+    for instance_name in instance_names {
+        match GenericName::parse(&instance_name).filter(|v| v.is_generic()) {
+            Some(generic_name) if !flatten_generics => generic_map
+                .entry(generic_name.name.clone())
+                .or_default()
+                .push((instance_name, generic_name)),
+            _ => flattened_names.push(instance_name),
+        }
+    }
+    generic_map.retain(|generic_name, instances| {
+        let arity = instances[0].1.args.len();
+        let is_generic = !components.schemas.contains_key(generic_name)
+            && instances.iter().all(|(instance_name, instance)| {
+                instance.args.len() == arity
+                    && components.schemas[instance_name].properties.is_some()
+            });
+        if !is_generic {
+            warn!("schema {} can not be generic, flatten it", generic_name);
+            flattened_names.extend(instances.iter().map(|v| v.0.clone()));
+        }
+        is_generic
+    });
+
+    // 去掉名称中的 «», 并修改所有的引用
+    let mut rename_map: HashMap<String, String> = HashMap::new();
+    for flattened_name in flattened_names {
+        let type_name = open_3_get_type_name_from_schema_ref(&flattened_name);
+        let unique_name = get_unique_name(&type_name, suffix_template, &|v| {
+            open_3_type_name_is_taken(components, v) || rename_map.values().any(|x| x.eq(v))
+        });
+        if !unique_name.eq(&type_name) {
+            warn!(
+                "schema {} type name {} already exists, rename to {}",
+                flattened_name, type_name, unique_name
+            );
+        }
+        if let Some(mut schema) = components.schemas.remove(&flattened_name) {
+            schema.title = Some(unique_name.clone());
+            components.schemas.insert(unique_name.clone(), schema);
+        }
+        rename_map.insert(flattened_name, unique_name);
+    }
+
+    // 生成泛型声明
+    for (generic_name, instances) in generic_map {
+        let components = &mut config.components;
+        let arity = instances[0].1.args.len();
+        let type_parameters: Vec<String> = if arity == 1 {
+            vec![String::from("T")]
+        } else {
+            (1..=arity).map(|v| format!("T{}", v)).collect()
+        };
+        let instance_schemas: Vec<(&Open3Schema, Vec<String>)> = instances
+            .iter()
+            .map(|(instance_name, instance)| {
+                (
+                    &components.schemas[instance_name],
+                    instance
+                        .args
+                        .iter()
+                        .map(|v| open_3_get_generic_type_name(v, &None))
+                        .collect(),
+                )
+            })
+            .collect();
+        let property_names: BTreeSet<&String> = instance_schemas
+            .iter()
+            .flat_map(|v| v.0.properties.iter().flatten().map(|x| x.0))
+            .collect();
+        let mut properties = HashMap::new();
+        for property_name in property_names {
+            let property_types: Vec<(Option<&Open3Schema>, String, &Vec<String>)> = instance_schemas
+                .iter()
+                .map(|(schema, args)| {
+                    let property = schema.properties.as_ref().and_then(|v| v.get(property_name));
+                    (
+                        property,
+                        property
+                            .map(|v| open_3_get_type_name_from_schema(v, None))
+                            .unwrap_or_default(),
+                        args,
+                    )
+                })
+                .collect();
+            let property_type = open_3_get_generic_property_type(
+                &property_types
+                    .iter()
+                    .filter(|v| v.0.is_some())
+                    .map(|v| (v.1.as_str(), v.2.as_slice()))
+                    .collect::<Vec<(&str, &[String])>>(),
+                &type_parameters,
+            )
+            .unwrap_or_else(|| {
+                warn!("generic {} property {} type is not consistent", generic_name, property_name);
+                String::from("any")
+            });
+            let Some(property) = property_types.iter().find_map(|v| v.0) else {
+                continue;
+            };
             properties.insert(
-                v.name.clone(),
+                property_name.to_string(),
                 Open3Schema {
-                    schema_type: Some("string".to_string()),
-                    description: v.description.clone(),
+                    ts_type: Some(property_type),
+                    description: property.description.clone(),
+                    example: property.example.clone(),
+                    examples: property.examples.clone(),
+                    read_only: property.read_only,
+                    write_only: property.write_only,
                     ..Default::default()
                 },
             );
-            if v.required {
-                required_vec.push(v.name.clone());
+        }
+        // 所有实例中都必须的属性才是必须的
+        let required: Vec<String> = properties
+            .keys()
+            .filter(|property_name| {
+                instance_schemas
+                    .iter()
+                    .all(|v| v.0.required.iter().flatten().any(|x| x.eq(*property_name)))
+            })
+            .cloned()
+            .collect();
+        let generic_schema = Open3Schema {
+            title: Some(generic_name.clone()),
+            description: instance_schemas[0].0.description.clone(),
+            schema_type: Some("object".into()),
+            properties: Some(properties),
+            required: Some(required),
+            type_parameters: Some(type_parameters),
+            ..Default::default()
+        };
+        for (instance_name, _) in instances.iter() {
+            if let Some(schema) = components.schemas.get_mut(instance_name) {
+                schema.generic_instance = true;
             }
-        });
+        }
+        components.schemas.insert(generic_name, generic_schema);
     }
-    let required_vec_is_empty = required_vec.is_empty();
-    let properties_is_empty = properties.is_empty();
-    if properties_is_empty {
-        return (String::from("void"), String::from("void"));
+
+    open_3_rename_schema_refs(config, &rename_map);
+}
+
+/// 判断属性是否需要去除，请求体中去除 readOnly 属性，响应中去除 writeOnly 属性
+fn open_3_is_omitted_property(property: &Open3Schema, is_request: bool) -> bool {
+    if is_request {
+        property.read_only == Some(true)
+    } else {
+        property.write_only == Some(true)
     }
-    let components_schema = Open3ComponentsSchema {
-        title: Some(type_name.clone()),
-        schema_type: "object".to_string(),
-        properties: Some(properties),
-        required: Some(required_vec),
-    };
-    schemas.insert(type_name.clone(), components_schema);
-    (
-        type_name_with_namespace.clone(),
-        if !required_vec_is_empty {
-            type_name_with_namespace
-        } else {
-            format!("{} | void", type_name_with_namespace)
-        },
-    )
 }
 
-/// 获取post请求的请求类型
-fn generate_post_request_type(
-    components: &mut Open3Components,
-    api_config: &Open3ApiConfig,
-    command_config: &CommandConfig,
-) -> (String, String) {
-    let application_json = api_config
-        .request_body
+/// 判断 schema（不包括引用的 schema）中是否有需要去除的属性
+fn open_3_has_omitted_property(schema: &Open3Schema, is_request: bool) -> bool {
+    let mut has_omitted = false;
+    schema.walk(&mut |v| {
+        has_omitted |= v
+            .properties
+            .iter()
+            .flatten()
+            .any(|(_, property)| open_3_is_omitted_property(property, is_request));
+    });
+    has_omitted
+}
+
+/// 获取 schema 中引用的所有 schema 名称
+fn open_3_get_schema_refs(schema: &Open3Schema) -> Vec<String> {
+    let mut schema_refs = vec![];
+    schema.walk(&mut |v| {
+        if let Some(schema_ref) = &v.schema_ref {
+            schema_refs.push(get_schema_name_from_schema_ref(schema_ref));
+        }
+    });
+    schema_refs
+}
+
+/// 去除 schema 中需要去除的属性，并将引用改为 rename_map 中对应的 schema
+fn open_3_omit_schema_properties(
+    schema: &mut Open3Schema,
+    is_request: bool,
+    rename_map: &HashMap<String, String>,
+) {
+    schema.walk_mut(&mut |v| {
+        if let Some(properties) = &mut v.properties {
+            let omitted: Vec<String> = properties
+                .iter()
+                .filter(|(_, property)| open_3_is_omitted_property(property, is_request))
+                .map(|(property_name, _)| property_name.clone())
+                .collect();
+            properties.retain(|property_name, _| !omitted.contains(property_name));
+            if let Some(required) = &mut v.required {
+                required.retain(|property_name| !omitted.contains(property_name));
+            }
+        }
+        let Some(schema_ref) = &v.schema_ref else {
+            return;
+        };
+        if let Some(name) = rename_map.get(&get_schema_name_from_schema_ref(schema_ref)) {
+            v.schema_ref = Some(format!("#/components/schemas/{}", name));
+        }
+    });
+}
+
+/// 生成请求体与响应使用的 schema
+///
+/// 请求体中去除 readOnly 属性，响应中去除 writeOnly 属性，嵌套的对象、组合类型的成员以及数组元素中的属性同样去除。
+/// 请求体或响应直接或间接引用的 schema 中存在需要去除的属性时，生成 UserRequest、UserResponse 这样的 schema，
+/// 并将请求体和响应中的引用改为新生成的 schema
+fn open_3_split_read_write_schemas(config: &mut Open3Config, suffix_template: &str) {
+    open_3_split_schemas(config, suffix_template, true);
+    open_3_split_schemas(config, suffix_template, false);
+}
+
+fn open_3_split_schemas(config: &mut Open3Config, suffix_template: &str, is_request: bool) {
+    let components = &mut config.components;
+    // 需要拆分的 schema：自身有需要去除的属性，或者引用了需要拆分的 schema
+    let mut split_names: HashSet<String> = components
+        .schemas
+        .iter()
+        .filter(|(_, schema)| open_3_has_omitted_property(schema, is_request))
+        .map(|(schema_name, _)| schema_name.clone())
+        .collect();
+    loop {
+        let referenced: Vec<String> = components
+            .schemas
+            .iter()
+            .filter(|(schema_name, schema)| {
+                !split_names.contains(*schema_name)
+                    && open_3_get_schema_refs(schema).iter().any(|v| split_names.contains(v))
+            })
+            .map(|(schema_name, _)| schema_name.clone())
+            .collect();
+        if referenced.is_empty() {
+            break;
+        }
+        split_names.extend(referenced);
+    }
+
+    // 请求体或响应中的 schema
+    let mut root_schemas: Vec<&mut Open3Schema> = vec![];
+    for requests in config.paths.values_mut() {
+        for api_config in [
+            &mut requests.get,
+            &mut requests.post,
+            &mut requests.put,
+            &mut requests.delete,
+            &mut requests.patch,
+            &mut requests.head,
+            &mut requests.options,
+            &mut requests.trace,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if is_request {
+                if let Some(request_body) = &mut api_config.request_body {
+                    root_schemas.extend(request_body.content.values_mut().map(|v| &mut v.schema));
+                }
+            } else {
+                for response in api_config.responses.values_mut() {
+                    root_schemas.extend(
+                        response
+                            .content
+                            .iter_mut()
+                            .flat_map(|v| v.values_mut())
+                            .flatten()
+                            .map(|v| &mut v.schema),
+                    );
+                }
+            }
+        }
+    }
+
+    // 只拆分请求体或响应中用到的 schema
+    let mut used_names: BTreeSet<String> = BTreeSet::new();
+    let mut pending: Vec<String> = root_schemas
+        .iter()
+        .flat_map(|v| open_3_get_schema_refs(v))
+        .collect();
+    while let Some(schema_name) = pending.pop() {
+        if !split_names.contains(&schema_name) || !used_names.insert(schema_name.clone()) {
+            continue;
+        }
+        if let Some(schema) = components.schemas.get(&schema_name) {
+            pending.extend(open_3_get_schema_refs(schema));
+        }
+    }
+
+    let mut rename_map: HashMap<String, String> = HashMap::new();
+    for schema_name in used_names.iter() {
+        open_3_get_split_schema_name(
+            schema_name,
+            components,
+            &used_names,
+            suffix_template,
+            is_request,
+            &mut rename_map,
+        );
+    }
+    for schema_name in used_names.iter() {
+        let (Some(schema), Some(split_name)) =
+            (components.schemas.get(schema_name), rename_map.get(schema_name))
+        else {
+            continue;
+        };
+        let mut split_schema = schema.clone();
+        open_3_omit_schema_properties(&mut split_schema, is_request, &rename_map);
+        split_schema.title = Some(split_name.clone());
+        // 无法作为泛型实例的 schema 生成普通的类型
+        split_schema.generic_instance = schema.generic_instance && split_name.contains('«');
+        components.schemas.insert(split_name.clone(), split_schema);
+    }
+    for schema in root_schemas {
+        open_3_omit_schema_properties(schema, is_request, &rename_map);
+    }
+}
+
+/// 获取拆分后的 schema 名称，如：User 则为 UserRequest 或 UserResponse
+///
+/// 泛型实例自身没有需要去除的属性，并且只引用了类型参数时，替换类型参数，
+/// 如：Result«User» 则为 Result«UserResponse»，生成的类型为 Result<UserResponse>
+fn open_3_get_split_schema_name(
+    schema_name: &str,
+    components: &Open3Components,
+    used_names: &BTreeSet<String>,
+    suffix_template: &str,
+    is_request: bool,
+    rename_map: &mut HashMap<String, String>,
+) -> String {
+    if let Some(split_name) = rename_map.get(schema_name) {
+        return split_name.clone();
+    }
+    let schema = &components.schemas[schema_name];
+    let generic_name = GenericName::parse(schema_name).filter(|v| {
+        schema.generic_instance && v.is_generic() && !open_3_has_omitted_property(schema, is_request)
+    });
+    if let Some(generic_name) = generic_name {
+        let arg_names = generic_name.arg_schema_names();
+        let only_args = open_3_get_schema_refs(schema)
+            .iter()
+            .filter(|v| used_names.contains(*v))
+            .all(|v| arg_names.contains(v));
+        if only_args {
+            let split_generic_name = open_3_replace_generic_args(
+                &generic_name,
+                components,
+                used_names,
+                suffix_template,
+                is_request,
+                rename_map,
+            );
+            let split_name = split_generic_name.schema_name();
+            if !components.schemas.contains_key(&split_name) {
+                rename_map.insert(schema_name.to_string(), split_name.clone());
+                return split_name;
+            }
+        }
+    }
+    let name = format!(
+        "{}{}",
+        open_3_get_type_name_from_schema_ref(schema_name),
+        if is_request { "Request" } else { "Response" }
+    );
+    let split_name = get_unique_name(&name, suffix_template, &|v| {
+        open_3_type_name_is_taken(components, v) || rename_map.values().any(|x| x.eq(v))
+    });
+    rename_map.insert(schema_name.to_string(), split_name.clone());
+    split_name
+}
+
+/// 将泛型名称中需要拆分的类型参数替换为拆分后的名称
+fn open_3_replace_generic_args(
+    generic_name: &GenericName,
+    components: &Open3Components,
+    used_names: &BTreeSet<String>,
+    suffix_template: &str,
+    is_request: bool,
+    rename_map: &mut HashMap<String, String>,
+) -> GenericName {
+    let args = generic_name
+        .args
+        .iter()
+        .map(|arg| {
+            let arg_name = arg.schema_name();
+            if used_names.contains(&arg_name) {
+                let split_name = open_3_get_split_schema_name(
+                    &arg_name,
+                    components,
+                    used_names,
+                    suffix_template,
+                    is_request,
+                    rename_map,
+                );
+                GenericName::parse(&split_name).unwrap_or_else(|| GenericName {
+                    name: split_name,
+                    args: vec![],
+                })
+            } else {
+                open_3_replace_generic_args(
+                    arg,
+                    components,
+                    used_names,
+                    suffix_template,
+                    is_request,
+                    rename_map,
+                )
+            }
+        })
+        .collect();
+    GenericName {
+        name: generic_name.name.clone(),
+        args,
+    }
+}
+
+/// 处理不同 schema 生成相同类型名称的冲突，如：User-Info 与 UserInfo、title 相同的 schema
+///
+/// 保留名称与类型名称一致的 schema（都不一致时保留排序后的第一个），
+/// 其余的按照 --name-collision-suffix 重命名并修改所有的引用
+fn open_3_resolve_type_name_collisions(config: &mut Open3Config, suffix_template: &str) {
+    let components = &mut config.components;
+    let mut type_name_map: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (schema_name, schema) in components.schemas.iter() {
+        if schema.generic_instance {
+            continue;
+        }
+        type_name_map
+            .entry(open_3_get_schema_type_name(schema_name, schema))
+            .or_default()
+            .push(schema_name.clone());
+    }
+    let mut rename_map: HashMap<String, String> = HashMap::new();
+    for (type_name, mut schema_names) in type_name_map {
+        if schema_names.len() < 2 {
+            continue;
+        }
+        schema_names.sort();
+        let kept_index = schema_names.iter().position(|v| v.eq(&type_name)).unwrap_or(0);
+        let kept_name = schema_names.remove(kept_index);
+        for schema_name in schema_names {
+            let unique_name = get_unique_name(&type_name, suffix_template, &|v| {
+                open_3_type_name_is_taken(components, v)
+            });
+            warn!(
+                "schema {} and {} generate the same type name {}, rename {} to {}",
+                kept_name, schema_name, type_name, schema_name, unique_name
+            );
+            if let Some(mut schema) = components.schemas.remove(&schema_name) {
+                schema.title = Some(unique_name.clone());
+                components.schemas.insert(unique_name.clone(), schema);
+            }
+            rename_map.insert(schema_name, unique_name);
+        }
+    }
+
+    open_3_rename_schema_refs(config, &rename_map);
+}
+
+/// 将引用重命名的 schema 的 $ref 修改为新的名称
+fn open_3_rename_schema_refs(config: &mut Open3Config, rename_map: &HashMap<String, String>) {
+    if rename_map.is_empty() {
+        return;
+    }
+    for schema in open_3_get_config_schemas_mut(config) {
+        schema.walk_mut(&mut |v| {
+            let Some(schema_ref) = &v.schema_ref else {
+                return;
+            };
+            if let Some(name) = rename_map.get(&get_schema_name_from_schema_ref(schema_ref)) {
+                v.schema_ref = Some(format!("#/components/schemas/{}", name));
+            }
+        });
+    }
+}
+
+/// 获取 schema 生成的类型名称
+fn open_3_get_schema_type_name(schema_name: &str, schema: &Open3ComponentsSchema) -> String {
+    open_3_get_type_name_from_schema_ref(schema.title.as_deref().unwrap_or(schema_name))
+}
+
+/// 判断名称是否已被 schema 或 schema 生成的类型使用
+///
+/// 比较转换为合法类型名称后的名称，如：User-Info 与 UserInfo 视为重名
+fn open_3_type_name_is_taken(components: &Open3Components, name: &str) -> bool {
+    let type_name = open_3_get_type_name_from_schema_ref(name);
+    components.schemas.iter().any(|(schema_name, schema)| {
+        schema_name.eq(name)
+            || (!schema.generic_instance && open_3_get_schema_type_name(schema_name, schema).eq(&type_name))
+    })
+}
+
+/// 推导泛型声明中属性的类型
+///
+/// property_types 为每个实例中属性的类型以及实例的类型参数，
+/// 将实例的类型参数替换为泛型参数作为候选，选择对所有实例都成立且泛型参数最多的候选
+/// 如：Result«User» 的 data: User 则返回 T
+fn open_3_get_generic_property_type(
+    property_types: &[(&str, &[String])],
+    type_parameters: &[String],
+) -> Option<String> {
+    let mut candidates: Vec<String> = vec![];
+    for (property_type, args) in property_types {
+        candidates.push(property_type.to_string());
+        // 优先替换较长的类型参数，避免替换其中的一部分
+        let mut arg_indexes: Vec<usize> = (0..args.len()).collect();
+        arg_indexes.sort_by_key(|v| std::cmp::Reverse(args[*v].len()));
+        let mut candidate = property_type.to_string();
+        for index in arg_indexes {
+            candidate = replace_type_token(&candidate, &args[index], &type_parameters[index]);
+        }
+        candidates.push(candidate);
+    }
+    candidates
+        .into_iter()
+        .filter(|candidate| {
+            property_types.iter().all(|(property_type, args)| {
+                let mut instance_type = candidate.clone();
+                for (type_parameter, arg) in type_parameters.iter().zip(args.iter()) {
+                    instance_type = replace_type_token(&instance_type, type_parameter, arg);
+                }
+                instance_type.eq(property_type)
+            })
+        })
+        .max_by_key(|candidate| {
+            type_parameters
+                .iter()
+                .map(|v| candidate.matches(v.as_str()).count())
+                .sum::<usize>()
+        })
+}
+
+/// 替换类型中完整的类型名称
+///
+/// 如：将 Array<User> 中的 User 替换为 T，不会替换 UserInfo 中的 User
+fn replace_type_token(source: &str, from: &str, to: &str) -> String {
+    if from.is_empty() {
+        return source.to_string();
+    }
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_' || c == '$' || c == '.';
+    let mut result = String::new();
+    let mut index = 0;
+    while let Some(position) = source[index..].find(from) {
+        let start = index + position;
+        let end = start + from.len();
+        let is_token = !source[..start].chars().last().is_some_and(is_identifier)
+            && !source[end..].chars().next().is_some_and(is_identifier);
+        if is_token {
+            result.push_str(&source[index..start]);
+            result.push_str(to);
+            index = end;
+        } else {
+            let next = start + from.chars().next().map(|c| c.len_utf8()).unwrap_or(1);
+            result.push_str(&source[index..next]);
+            index = next;
+        }
+    }
+    result.push_str(&source[index..]);
+    result
+}
+
+/// 生成泛型名称对应的类型
+///
+/// List、Set 等集合生成数组，Map 生成 Record，java 基本类型转换为 ts 类型，其他名称视为 schema
+/// 如：Result«List«User»» 则返回 Result<Array<User>>
+fn open_3_get_generic_type_name(generic_name: &GenericName, namespace: &Option<String>) -> String {
+    lazy_static! {
+        static ref JAVA_TYPE_MAP: HashMap<&'static str, &'static str> = {
+            let mut m = HashMap::new();
+            m.insert("String", "string");
+            m.insert("string", "string");
+            m.insert("int", "number");
+            m.insert("Integer", "number");
+            m.insert("integer", "number");
+            m.insert("long", "number");
+            m.insert("Long", "number");
+            m.insert("short", "number");
+            m.insert("Short", "number");
+            m.insert("double", "number");
+            m.insert("Double", "number");
+            m.insert("float", "number");
+            m.insert("Float", "number");
+            m.insert("number", "number");
+            m.insert("BigDecimal", "number");
+            m.insert("BigInteger", "number");
+            m.insert("boolean", "boolean");
+            m.insert("Boolean", "boolean");
+            m.insert("object", "any");
+            m.insert("Object", "any");
+            m.insert("JsonNode", "any");
+            m.insert("Void", "void");
+            m.insert("void", "void");
+            m
+        };
+    }
+    let args: Vec<String> = generic_name
+        .args
+        .iter()
+        .map(|v| open_3_get_generic_type_name(v, namespace))
+        .collect();
+    let namespace_prefix = namespace.as_ref().map(|v| format!("{}.", v)).unwrap_or_default();
+    match (generic_name.name.as_str(), &args[..]) {
+        ("List" | "Set" | "Collection" | "Iterable" | "ArrayList" | "LinkedList" | "HashSet", [item]) => {
+            format!("Array<{}>", item)
+        }
+        ("Map" | "HashMap" | "LinkedHashMap" | "TreeMap", [key, value]) => {
+            format!("Record<{}, {}>", key, value)
+        }
+        (name, []) => match JAVA_TYPE_MAP.get(name) {
+            Some(ts_type) => ts_type.to_string(),
+            None => format!("{}{}", namespace_prefix, open_3_get_type_name_from_schema_ref(name)),
+        },
+        (name, args) => format!(
+            "{}{}<{}>",
+            namespace_prefix,
+            ts_type_identifier(name),
+            args.join(", ")
+        ),
+    }
+}
+
+/// 将属性中的匿名对象提升到 components 中
+///
+/// 提升后的名称为 父类型名称+属性名称，如：User 的 address 属性则为 UserAddress，
+/// 数组属性使用数组元素的 schema，重名时添加序号。
+/// 泛型实例不生成类型，其中的匿名对象由泛型声明中的类型参数表示，所以不提升
+fn open_3_hoist_inline_objects(components: &mut Open3Components, suffix_template: &str) {
+    let mut schema_names: Vec<String> = components
+        .schemas
+        .iter()
+        .filter(|(_, schema)| !schema.generic_instance)
+        .map(|(schema_name, _)| schema_name.clone())
+        .collect();
+    schema_names.sort();
+    let mut pending: Vec<String> = schema_names.into_iter().rev().collect();
+    while let Some(schema_name) = pending.pop() {
+        let Some(mut schema) = components.schemas.remove(&schema_name) else {
+            continue;
+        };
+        let parent_name = open_3_get_type_name_from_schema_ref(
+            schema.title.as_deref().unwrap_or(&schema_name),
+        );
+        let mut hoisted_vec: Vec<(String, Open3Schema)> = vec![];
+        let is_taken = |name: &str| {
+            name.eq(&schema_name) || name.eq(&parent_name) || open_3_type_name_is_taken(components, name)
+        };
+        open_3_take_inline_objects(
+            &mut schema,
+            &parent_name,
+            &is_taken,
+            suffix_template,
+            &mut hoisted_vec,
+        );
+        components.schemas.insert(schema_name, schema);
+        // 提升的 schema 中可能还有匿名对象
+        for (hoisted_name, hoisted_schema) in hoisted_vec.into_iter().rev() {
+            components.schemas.insert(hoisted_name.clone(), hoisted_schema);
+            pending.push(hoisted_name);
+        }
+    }
+}
+
+/// 将 schema 属性中的匿名对象替换为引用，被替换的 schema 放入 hoisted_vec
+fn open_3_take_inline_objects(
+    holder: &mut Open3Schema,
+    parent_name: &str,
+    is_taken: &dyn Fn(&str) -> bool,
+    suffix_template: &str,
+    hoisted_vec: &mut Vec<(String, Open3Schema)>,
+) {
+    if let Some(properties) = &mut holder.properties {
+        let mut property_names: Vec<String> = properties.keys().cloned().collect();
+        property_names.sort();
+        for property_name in property_names {
+            let Some(mut property) = properties.get_mut(&property_name) else {
+                continue;
+            };
+            // 数组使用数组元素的 schema
+            while property.properties.is_none() && property.items.is_some() {
+                property = property.items.as_deref_mut().expect("array items");
+            }
+            if property.properties.is_none()
+                || property.schema_ref.is_some()
+                || property.is_composition()
+            {
+                continue;
+            }
+            let hoisted_name =
+                format!("{}{}", parent_name, capitalize(&join_identifier_words(&property_name)));
+            let unique_name = get_unique_name(&hoisted_name, suffix_template, &|v| {
+                is_taken(v) || hoisted_vec.iter().any(|x| x.0.eq(v))
+            });
+            if !unique_name.eq(&hoisted_name) {
+                warn!("type name {} already exists, rename to {}", hoisted_name, unique_name);
+            }
+            let mut hoisted_schema = std::mem::take(property);
+            hoisted_schema.title = Some(unique_name.clone());
+            property.schema_ref = Some(format!("#/components/schemas/{}", unique_name));
+            property.description = hoisted_schema.description.clone();
+            // 可为空、只读、只写属于属性本身，保留在引用上
+            property.nullable = hoisted_schema.nullable.take();
+            property.read_only = hoisted_schema.read_only.take();
+            property.write_only = hoisted_schema.write_only.take();
+            hoisted_vec.push((unique_name, hoisted_schema));
+        }
+    }
+    for member in holder.all_of.iter_mut().flatten() {
+        open_3_take_inline_objects(member, parent_name, is_taken, suffix_template, hoisted_vec);
+    }
+}
+
+/// 处理OpenApi3的类型
+///
+/// 转换成易处理的 OpenApiRequester 类型
+///
+/// 支持 get post put delete patch head options trace
+fn open_3_get_api_list(
+    config: &mut Open3Config,
+    command_config: &CommandConfig,
+) -> (ApiList, ApiList) {
+    let converted =
+        open_3_get_converted_schemas(&config.components, &command_config.name_collision_suffix);
+    let mut paths_vec: Vec<(&String, &Open3Requests)> = config.paths.iter().collect();
+    paths_vec.sort_by(|a, b| a.0.cmp(b.0));
+    let mut api_list: Vec<(String, OpenApiRequester)> = vec![];
+    let mut all_api_list: Vec<(String, OpenApiRequester)> = vec![];
+    for (url, requests) in paths_vec {
+        for (method, request) in requests.iter() {
+            if let Some(api_config) = request {
+                let api_config = &Open3ApiConfig {
+                    parameters: open_3_merge_parameters(requests, api_config, &config.components),
+                    ..api_config.clone()
+                };
+                let module: &String = &api_config.tags[0];
+                let operation_id = &api_config.operation_id;
+                let is_form = open_3_is_form(&method, api_config);
+                let is_query_method = QUERY_METHODS.contains(&method.as_str());
+                let parameters: Vec<&Open3Parameters> =
+                    api_config.parameters.iter().flatten().collect();
+                let request_type = open_3_get_request_type_name(
+                    &method,
+                    &mut config.components,
+                    api_config,
+                    command_config,
+                );
+                // 有请求体的请求 地址、query、header、cookie参数单独生成类型
+                let parameters_type = if !is_query_method && !parameters.is_empty() {
+                    Some(generate_parameters_type(
+                        &mut config.components,
+                        api_config,
+                        command_config,
+                        &parameters,
+                    ))
+                } else {
+                    None
+                };
+                let open_api_requester = OpenApiRequester {
+                    summary: if let Some(summary) = &api_config.summary {
+                        summary.to_string()
+                    } else {
+                        String::new()
+                    },
+                    method: method.clone(),
+                    operation_id: format!(
+                        "{}{}",
+                        command_config
+                            .operation_prefix
+                            .as_ref()
+                            .unwrap_or(&"".to_string()),
+                        operation_id
+                    ),
+                    url: url.to_string(),
+                    request_schema_name: request_type.0,
+                    // 可作为调用方法的参数类型， 已经处理是否可选
+                    request_type_name: request_type.1,
+                    // content_type: String::from("application/json"),
+                    response_converter: if command_config.wrap.is_none() {
+                        open_3_get_response_schema(api_config).and_then(|schema| {
+                            open_3_get_convert_expression(schema, "res", &converted, "converters.")
+                        })
+                    } else {
+                        None
+                    },
+                    serialize_body: !is_form
+                        && api_config
+                            .request_body
+                            .as_ref()
+                            .and_then(|v| v.get_content("application/json"))
+                            .is_some_and(|v| {
+                                open_3_schema_contains_bigint(&v.schema, &config.components)
+                            }),
+                    response_type_name: open_3_get_response_type_name(api_config, command_config),
+                    is_form,
+                    parameters: parameters
+                        .iter()
+                        .map(|v| OpenApiParameter {
+                            name: v.name.clone(),
+                            parameter_in: v.parameters_in.clone(),
+                        })
+                        .collect(),
+                    parameters_schema_name: parameters_type.as_ref().map(|v| v.0.clone()),
+                    parameters_type_name: parameters_type.map(|v| v.1),
+                    reexport_module: None,
+                };
+                all_api_list.push((module.to_string(), open_api_requester.clone()));
+                // 如果已经指定了tag， 则忽略其他tag
+                let mut modules: Vec<&String> = vec![];
+                for tag in api_config.tags.iter() {
+                    if !modules.contains(&tag)
+                        && (command_config.tags.is_empty() || command_config.tags.contains(tag))
+                    {
+                        modules.push(tag);
+                    }
+                }
+                match command_config.multi_tag {
+                    MultiTagStyle::First => {
+                        if let Some(first_module) = modules.first() {
+                            api_list.push((first_module.to_string(), open_api_requester));
+                        }
+                    }
+                    MultiTagStyle::Duplicate => {
+                        for module in modules {
+                            api_list.push((module.to_string(), open_api_requester.clone()));
+                        }
+                    }
+                    // 第一个选中的 tag 生成接口调用，其余的 tag 重新导出
+                    MultiTagStyle::Reexport => {
+                        if let Some((primary_module, other_modules)) = modules.split_first() {
+                            api_list.push((primary_module.to_string(), open_api_requester.clone()));
+                            for other_module in other_modules {
+                                api_list.push((
+                                    other_module.to_string(),
+                                    OpenApiRequester {
+                                        reexport_module: Some(primary_module.to_string()),
+                                        ..open_api_requester.clone()
+                                    },
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    (api_list, all_api_list)
+}
+
+/// 获取选中的接口用到的 schema
+///
+/// 从接口的参数、请求体、响应以及生成的请求类型出发，递归查找引用的 schema，
+/// 泛型实例引用时同时包含泛型声明以及类型参数
+fn open_3_get_reachable_schemas(
+    config: &Open3Config,
+    api_list: &ApiList,
+    command_config: &CommandConfig,
+) -> HashSet<String> {
+    let selected: HashSet<(&str, &str)> = api_list
+        .iter()
+        .map(|(_, v)| (v.method.as_str(), v.url.as_str()))
+        .collect();
+    let mut pending: Vec<String> = vec![];
+    let mut collect_refs = |schema: &Open3Schema| {
+        schema.walk(&mut |v| {
+            if let Some(schema_ref) = &v.schema_ref {
+                pending.extend(open_3_get_ref_schema_names(schema_ref));
+            }
+        })
+    };
+    for (url, requests) in config.paths.iter() {
+        for (method, api_config) in requests.iter() {
+            let Some(api_config) = api_config else {
+                continue;
+            };
+            if !selected.contains(&(method.as_str(), url.as_str())) {
+                continue;
+            }
+            let parameters =
+                open_3_merge_parameters(requests, api_config, &config.components);
+            for parameter in parameters.iter().flatten() {
+                if let Some(schema) = parameter.get_schema() {
+                    collect_refs(schema);
+                }
+            }
+            for content in api_config.request_body.iter().flat_map(|v| v.content.values()) {
+                collect_refs(&content.schema);
+            }
+            for response in api_config.responses.values() {
+                for content in response.content.iter().flat_map(|v| v.values()).flatten() {
+                    collect_refs(&content.schema);
+                }
+            }
+        }
+    }
+    // 生成的 Query、Params 类型
+    let namespace_prefix = command_config
+        .namespace
+        .as_ref()
+        .map(|v| format!("{}.", v))
+        .unwrap_or_default();
+    for (_, requester) in api_list.iter() {
+        for schema_name in std::iter::once(&requester.request_schema_name)
+            .chain(requester.parameters_schema_name.iter())
+        {
+            pending.push(schema_name.trim_start_matches(&namespace_prefix).to_string());
+        }
+    }
+
+    let mut reachable_schemas: HashSet<String> = HashSet::new();
+    while let Some(schema_name) = pending.pop() {
+        let Some(schema) = config.components.schemas.get(&schema_name) else {
+            continue;
+        };
+        if !reachable_schemas.insert(schema_name) {
+            continue;
+        }
+        schema.walk(&mut |v| {
+            if let Some(schema_ref) = &v.schema_ref {
+                pending.extend(open_3_get_ref_schema_names(schema_ref));
+            }
+        });
+    }
+    reachable_schemas
+}
+
+/// 获取引用的 schema 名称
+///
+/// 泛型实例同时返回泛型名称以及类型参数中的名称，如：Result«List«User»» 则返回 Result«List«User»»、Result、List、User
+fn open_3_get_ref_schema_names(schema_ref: &str) -> Vec<String> {
+    let schema_name = get_schema_name_from_schema_ref(schema_ref);
+    let mut schema_names = vec![schema_name.clone()];
+    let mut generic_names: Vec<GenericName> = GenericName::parse(&schema_name)
+        .filter(|v| v.is_generic())
+        .into_iter()
+        .collect();
+    while let Some(generic_name) = generic_names.pop() {
+        schema_names.push(generic_name.name);
+        generic_names.extend(generic_name.args);
+    }
+    schema_names
+}
+
+/// 获取接口用到的模块
+///
+/// 顶层声明的 tag 在前，未声明的 tag 按照使用顺序排在后面
+fn open_3_get_module_list(
+    config: &Open3Config,
+    api_list: &ApiList,
+    command_config: &CommandConfig,
+) -> Vec<OpenApiModule> {
+    let mut module_names: Vec<&String> = vec![];
+    for (module, _) in api_list.iter() {
+        if !module_names.contains(&module) {
+            module_names.push(module);
+        }
+    }
+    let tag_index = |name: &String| {
+        config
+            .tags
+            .iter()
+            .position(|v| v.name.eq(name))
+            .unwrap_or(config.tags.len())
+    };
+    module_names.sort_by_key(|v| tag_index(v));
+    let mut dir_names: Vec<String> = vec![];
+    module_names
+        .into_iter()
+        .map(|name| {
+            let description = config
+                .tags
+                .iter()
+                .find(|v| v.name.eq(name))
+                .map(|v| v.description.trim())
+                .filter(|v| !v.is_empty())
+                .unwrap_or(name)
+                .to_string();
+            let dir_name = open_3_get_module_dir_name(name, &description, &dir_names, command_config);
+            dir_names.push(dir_name.clone());
+            OpenApiModule {
+                description,
+                name: name.to_string(),
+                dir_name,
+            }
+        })
+        .collect()
+}
+
+/// 生成模块的目录名称
+///
+/// --module-name-map 中指定的 tag 使用指定的名称，否则按照 --module-naming 生成，
+/// 去掉文件名中不安全的字符，与已有的目录重名（忽略大小写）时添加后缀
+fn open_3_get_module_dir_name(
+    name: &str,
+    description: &str,
+    dir_names: &[String],
+    command_config: &CommandConfig,
+) -> String {
+    let dir_name = match command_config.module_name_map.get(name) {
+        Some(dir_name) => dir_name.to_string(),
+        None => match command_config.module_naming {
+            ModuleNaming::Description => description.split_whitespace().collect(),
+            ModuleNaming::Name => name.to_string(),
+            ModuleNaming::Kebab => to_kebab_case(name),
+            ModuleNaming::Camel => to_camel_case(name),
+        },
+    };
+    let mut safe_name = sanitize_file_name(&dir_name);
+    if safe_name.is_empty() {
+        safe_name = sanitize_file_name(&to_camel_case(name));
+    }
+    if safe_name.is_empty() {
+        safe_name = String::from("module");
+    }
+    if !safe_name.eq(&dir_name) {
+        warn!("module {} directory name {} is not safe, use {}", name, dir_name, safe_name);
+    }
+    let unique_name = get_unique_name(&safe_name, &command_config.name_collision_suffix, &|v| {
+        dir_names.iter().any(|x| x.to_lowercase().eq(&v.to_lowercase()))
+    });
+    if !unique_name.eq(&safe_name) {
+        warn!("module {} directory name {} already exists, use {}", name, safe_name, unique_name);
+    }
+    unique_name
+}
+
+/// 合并路径参数与请求参数，并解析 components.parameters 中的参数引用
+fn open_3_merge_parameters(
+    requests: &Open3Requests,
+    api_config: &Open3ApiConfig,
+    components: &Open3Components,
+) -> Option<Vec<Open3Parameters>> {
+    let merged = merge_parameters(&requests.parameters, &api_config.parameters, |v| {
+        open_3_resolve_parameter(v, components)
+    });
+    if merged.is_empty() {
+        None
+    } else {
+        Some(merged)
+    }
+}
+
+/// 解析 #/components/parameters/xxx 形式的参数引用
+fn open_3_resolve_parameter(
+    parameter: &Open3Parameters,
+    components: &Open3Components,
+) -> Option<Open3Parameters> {
+    let mut parameter = parameter.clone();
+    // 引用的参数可能还是一个引用 限制解析次数避免循环引用
+    for _ in 0..16 {
+        let Some(parameter_ref) = &parameter.parameter_ref else {
+            return Some(parameter);
+        };
+        let name = get_schema_name_from_schema_ref(parameter_ref);
+        match components.parameters.as_ref().and_then(|v| v.get(&name)) {
+            Some(resolved) => parameter = resolved.clone(),
+            None => {
+                warn!("parameter {} not found, skip", parameter_ref);
+                return None;
+            }
+        }
+    }
+    warn!("parameter {:?} is a circular reference, skip", parameter.parameter_ref);
+    None
+}
+
+/// 判断是否是表单请求
+///
+/// post请求的requestBody为表单类型时视为form，地址、query等参数不影响请求体类型
+fn open_3_is_form(method: &str, api_config: &Open3ApiConfig) -> bool {
+    if !method.eq("post") {
+        return false;
+    }
+    api_config.request_body.as_ref().is_some_and(|x| {
+        x.get_content("multipart/form-data").is_some()
+            || x.get_content("application/x-www-form-urlencoded").is_some()
+    })
+}
+
+/// 获取响应类型名称
+fn open_3_get_response_type_name(
+    api_config: &Open3ApiConfig,
+    command_config: &CommandConfig,
+) -> String {
+    let mut res_type = open_3_get_response_schema(api_config)
+        .map(|schema| open_3_get_type_name_from_schema(schema, command_config.namespace.clone()));
+    // 支持传入一个包装层 对response类型进行包装
+    if let Some(wrap) = &command_config.wrap {
+        if let Some(res_t) = res_type {
+            res_type = Some(format!("{wrap}<{res_t}>"));
+        }
+    }
+    res_type.get_or_insert("void".into()).clone()
+}
+
+/// 获取生成响应类型使用的 schema
+fn open_3_get_response_schema(api_config: &Open3ApiConfig) -> Option<&Open3Schema> {
+    api_config
+        .responses
+        .get("200")
+        .and_then(|x| x.content.as_ref())
+        .and_then(|v| v.values().flatten().last())
+        .map(|v| &v.schema)
+}
+
+/// 获取需要转换响应值的 components schema 名称以及对应的转换函数名称
+///
+/// 引用的 schema 需要转换时 当前 schema 也需要转换，循环直到结果不再变化，
+/// 转换函数名称重名时按照 suffix_template 生成名称
+fn open_3_get_converted_schemas(
+    components: &Open3Components,
+    suffix_template: &str,
+) -> HashMap<String, String> {
+    let mut converted: HashMap<String, String> = HashMap::new();
+    loop {
+        let mut changed = false;
+        for (schema_name, schema) in components.schemas.iter() {
+            if !converted.contains_key(schema_name)
+                && open_3_get_convert_expression(schema, "value", &converted, "").is_some()
+            {
+                converted.insert(schema_name.to_string(), String::new());
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    let mut schema_name_vec: Vec<String> = converted.keys().cloned().collect();
+    schema_name_vec.sort();
+    let mut converter_name_vec: Vec<String> = vec![];
+    for schema_name in schema_name_vec {
+        let converter_name = get_unique_name(
+            &open_3_get_converter_name(&schema_name),
+            suffix_template,
+            &|v| converter_name_vec.iter().any(|x| x.eq(v)),
+        );
+        converter_name_vec.push(converter_name.clone());
+        converted.insert(schema_name, converter_name);
+    }
+    converted
+}
+
+/// 获取 schema 对应的转换函数名称
+///
+/// 如：User 则返回 convertUser
+fn open_3_get_converter_name(schema_name: &str) -> String {
+    format!("convert{}", open_3_get_type_name_from_schema_ref(schema_name))
+}
+
+/// 判断 schema（包括引用的 schema）中是否有映射为 bigint 的值
+fn open_3_schema_contains_bigint(schema: &Open3Schema, components: &Open3Components) -> bool {
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut pending: Vec<&Open3Schema> = vec![schema];
+    while let Some(schema) = pending.pop() {
+        let mut contains_bigint = false;
+        schema.walk(&mut |v| {
+            contains_bigint |= v.ts_type.as_ref().is_some_and(|x| x.contains("bigint"));
+        });
+        if contains_bigint {
+            return true;
+        }
+        for schema_name in open_3_get_schema_refs(schema) {
+            if !visited.insert(schema_name.clone()) {
+                continue;
+            }
+            if let Some(schema) = components.schemas.get(&schema_name) {
+                pending.push(schema);
+            }
+        }
+    }
+    false
+}
+
+/// 生成响应值的转换表达式，不需要转换时返回 None
+///
+/// value 为被转换的值的表达式，prefix 为转换函数所在模块的前缀
+///
+/// - 类型映射的值使用映射的转换表达式
+/// - 引用的 schema 调用对应的转换函数
+/// - 数组、对象、Record 转换其中的元素，allOf 依次使用每个 schema 转换
+/// - oneOf、anyOf 无法确定具体类型 不转换
+fn open_3_get_convert_expression(
+    schema: &Open3Schema,
+    value: &str,
+    converted: &HashMap<String, String>,
+    prefix: &str,
+) -> Option<String> {
+    if let Some(schema_ref) = &schema.schema_ref {
+        let schema_name = get_schema_name_from_schema_ref(schema_ref);
+        return converted
+            .get(&schema_name)
+            .map(|converter_name| format!("{prefix}{converter_name}({value})"));
+    }
+    if let Some(convert) = &schema.ts_convert {
+        return Some(format!("{prefix}mapValue({value}, (value) => {convert})"));
+    }
+    if let Some(all_of) = &schema.all_of {
+        let mut expression = value.to_string();
+        for member in all_of {
+            if let Some(member_expression) =
+                open_3_get_convert_expression(member, &expression, converted, prefix)
+            {
+                expression = member_expression;
+            }
+        }
+        return (!expression.eq(value)).then_some(expression);
+    }
+    if let Some(properties) = &schema.properties {
+        let mut properties: Vec<(&String, &Open3Schema)> = properties.iter().collect();
+        properties.sort_by(|a, b| a.0.cmp(b.0));
+        let property_expressions: Vec<String> = properties
+            .into_iter()
+            .filter_map(|(property_name, property)| {
+                let property_name = ts_literal_type(&Value::String(property_name.to_string()));
+                open_3_get_convert_expression(
+                    property,
+                    &format!("value[{property_name}]"),
+                    converted,
+                    prefix,
+                )
+                .map(|v| format!("{property_name}: {v}"))
+            })
+            .collect();
+        if property_expressions.is_empty() {
+            return None;
+        }
+        return Some(format!(
+            "{prefix}mapValue({value}, (value) => ({{ ...value, {} }}))",
+            property_expressions.join(", ")
+        ));
+    }
+    if let Some(items) = &schema.items {
+        return open_3_get_convert_expression(items, "item", converted, prefix)
+            .map(|v| format!("{prefix}mapArray({value}, (item) => {v})"));
+    }
+    if let Some(Open3AdditionalProperties::Schema(additional_properties)) =
+        &schema.additional_properties
+    {
+        return open_3_get_convert_expression(additional_properties, "item", converted, prefix)
+            .map(|v| format!("{prefix}mapRecord({value}, (item) => {v})"));
+    }
+    None
+}
+
+/// 获取请求参数类型名称
+///
+/// 如若当前的请求参数类型是可选的 则会在类型后面拼接 | void，
+/// 将拼接结果作为第二个参数返回
+///
+/// 如果命令行参数指定了namespace 则会将namespace拼接在类型前
+///
+/// 对于get/delete/head/options/trace请求
+///
+/// - 根据api_config判断当前请求类型
+/// - 如果使用path或query参数
+/// - 将path和query参数混合生成新的类型塞进components（可使用operation_id + 关键字作为地址参数的类型名称）
+fn open_3_get_request_type_name(
+    method: &str,
+    components: &mut Open3Components,
+    api_config: &Open3ApiConfig,
+    command_config: &CommandConfig,
+) -> (String, String) {
+    if QUERY_METHODS.contains(&method) {
+        generate_get_request_type(components, api_config, command_config)
+    } else {
+        generate_post_request_type(components, api_config, command_config)
+    }
+}
+
+/// 生成get请求的请求类型并塞进components
+///
+/// 参数类型使用参数的schema生成，未声明schema的参数统一成string
+fn generate_get_request_type(
+    components: &mut Open3Components,
+    api_config: &Open3ApiConfig,
+    command_config: &CommandConfig,
+) -> (String, String) {
+    let parameters: Vec<&Open3Parameters> = api_config.parameters.iter().flatten().collect();
+    generate_parameters_type(components, api_config, command_config, &parameters)
+}
+
+/// 将参数生成 {operation_id}Query 类型并塞进components
+fn generate_parameters_type(
+    components: &mut Open3Components,
+    api_config: &Open3ApiConfig,
+    command_config: &CommandConfig,
+    parameters: &[&Open3Parameters],
+) -> (String, String) {
+    let mut properties = HashMap::new();
+    let mut required_vec = Vec::new();
+    parameters.iter().for_each(|v| {
+        // 未声明类型的参数视为string
+        let mut schema = v.get_schema().cloned().unwrap_or_else(|| Open3Schema {
+            schema_type: Some("string".into()),
+            ..Default::default()
+        });
+        if v.description.is_some() {
+            schema.description = v.description.clone();
+        }
+        properties.insert(v.name.clone(), schema);
+        if v.required {
+            required_vec.push(v.name.clone());
+        }
+    });
+    let required_vec_is_empty = required_vec.is_empty();
+    let properties_is_empty = properties.is_empty();
+    if properties_is_empty {
+        return (String::from("void"), String::from("void"));
+    }
+    let type_name = open_3_get_generated_type_name(
+        components,
+        &open_3_get_operation_type_name(&api_config.operation_id, "Query"),
+        &command_config.name_collision_suffix,
+    );
+    let type_name_with_namespace = if let Some(namespace) = &command_config.namespace {
+        format!("{}.{}", namespace, type_name)
+    } else {
+        type_name.clone()
+    };
+    let components_schema = Open3ComponentsSchema {
+        title: Some(type_name.clone()),
+        schema_type: Some("object".into()),
+        properties: Some(properties),
+        required: Some(required_vec),
+        ..Default::default()
+    };
+    components.schemas.insert(type_name.clone(), components_schema);
+    (
+        type_name_with_namespace.clone(),
+        if !required_vec_is_empty {
+            type_name_with_namespace
+        } else {
+            format!("{} | void", type_name_with_namespace)
+        },
+    )
+}
+
+/// 获取 operation 生成的类型名称，转换为合法的类型名称
+///
+/// 如：get-user 则返回 GetUserQuery，2fa-verify 则返回 _2faVerifyQuery
+fn open_3_get_operation_type_name(operation_id: &str, suffix: &str) -> String {
+    ts_type_identifier(&format!(
+        "{}{}",
+        capitalize(&join_identifier_words(operation_id)),
+        suffix
+    ))
+}
+
+/// 获取生成的请求类型名称，与已有的类型重名时添加后缀
+fn open_3_get_generated_type_name(
+    components: &Open3Components,
+    type_name: &str,
+    suffix_template: &str,
+) -> String {
+    let unique_name = get_unique_name(type_name, suffix_template, &|v| {
+        open_3_type_name_is_taken(components, v)
+    });
+    if !unique_name.eq(type_name) {
+        warn!("type name {} already exists, rename to {}", type_name, unique_name);
+    }
+    unique_name
+}
+
+/// 获取post请求的请求类型
+fn generate_post_request_type(
+    components: &mut Open3Components,
+    api_config: &Open3ApiConfig,
+    command_config: &CommandConfig,
+) -> (String, String) {
+    let application_json = api_config
+        .request_body
         .as_ref()
-        .and_then(|x| x.content.get("application/json"));
+        .and_then(|x| x.get_content("application/json"));
     if let Some(schema_ref) = application_json.and_then(|x| x.schema.schema_ref.as_ref()) {
-        if let Some(type_res) = generate_type(&schema_ref, components, command_config) {
+        if let Some(type_res) = generate_type(schema_ref, components, command_config) {
             return type_res;
         }
     }
-    if let Some(properties) = application_json.and_then(|v| v.schema.properties.clone()) {
-        if let Some(schema) = application_json.and_then(|v| Some(v.schema.clone())) {
+    if let Some(schema) = application_json.map(|v| &v.schema) {
+        if schema.properties.is_some() {
             // 存在匿名的schema 将schema塞进components
-            let type_name = format!("{}Params", capitalize(&api_config.operation_id));
+            let type_name = open_3_get_generated_type_name(
+                components,
+                &open_3_get_operation_type_name(&api_config.operation_id, "Params"),
+                &command_config.name_collision_suffix,
+            );
             let components_schema = Open3ComponentsSchema {
                 title: Some(type_name.clone()),
-                schema_type: schema.schema_type.unwrap(),
-                properties: Some(properties),
-                required: schema.required,
+                ..schema.clone()
             };
             components
                 .schemas
@@ -339,6 +1990,11 @@ fn generate_post_request_type(
                 return type_res;
             }
         }
+        // 数组请求体直接使用数组类型
+        if schema.schema_type.as_ref().is_some_and(|v| v.contains("array")) {
+            let type_name = open_3_get_type_name_from_schema(schema, command_config.namespace.clone());
+            return (type_name.clone(), type_name);
+        }
     }
     (String::from("void"), String::from("void"))
 }
@@ -349,75 +2005,524 @@ fn generate_type(
     command_config: &CommandConfig,
 ) -> Option<(String, String)> {
     let is_required = open_3_schema_is_required(components, schema_ref);
-    let type_name = open_3_get_type_name_from_schema_ref(schema_ref);
     // 如果指定namespace 则需要在类型前添加namespace.
-    let type_name = if let Some(namespace) = &command_config.namespace {
-        format!("{}.{}", namespace, type_name)
+    let type_name = open_3_get_type_name_from_schema(
+        &Open3Schema {
+            schema_ref: Some(schema_ref.to_string()),
+            ..Default::default()
+        },
+        command_config.namespace.clone(),
+    );
+    let mut type_name_clone = type_name.clone();
+    if !type_name.eq("void") {
+        return Some((
+            type_name,
+            if !is_required {
+                type_name_clone.push_str(" | void");
+                type_name_clone
+            } else {
+                type_name_clone
+            },
+        ));
+    }
+    None
+}
+
+/// 根据schema生成响应类型名称
+///
+/// 如果命令行参数指定了namesapce 则会将namespace拼接在类型前
+///
+/// 支持 3.1 的类型数组（如 ["string", "null"] 生成 string | null）、const 字面量类型以及 prefixItems 元组类型，
+/// enum 生成字面量联合类型
+fn open_3_get_type_name_from_schema(schema: &Open3Schema, namespace: Option<String>) -> String {
+    // 3.0 nullable 追加 | null
+    if schema.nullable == Some(true) {
+        let type_name = open_3_get_type_name_from_schema(
+            &Open3Schema {
+                nullable: None,
+                ..schema.clone()
+            },
+            namespace,
+        );
+        return if type_name.split(" | ").any(|v| v.eq("null")) {
+            type_name
+        } else if type_name.contains(" & ") {
+            format!("({}) | null", type_name)
+        } else {
+            format!("{} | null", type_name)
+        };
+    }
+    if let Some(schema_ref) = &schema.schema_ref {
+        // 名称中仍然包含 «» 的 schema 是泛型实例
+        if let Some(generic_name) =
+            GenericName::parse(&get_schema_name_from_schema_ref(schema_ref)).filter(|v| v.is_generic())
+        {
+            return open_3_get_generic_type_name(&generic_name, &namespace);
+        }
+        let schema_ref = open_3_get_type_name_from_schema_ref(schema_ref);
+        return if let Some(namespace) = namespace {
+            format!("{}.{}", namespace, schema_ref)
+        } else {
+            schema_ref
+        };
+    }
+    if schema.is_composition() {
+        return open_3_get_composition_type(schema, namespace, false);
+    }
+    if let Some(const_value) = &schema.const_value {
+        return ts_literal_type(const_value);
+    }
+    // 枚举值生成字面量联合类型
+    if let Some(property_enum) = schema.property_enum.as_ref().filter(|v| !v.is_empty()) {
+        let mut literal_types: Vec<String> = vec![];
+        for literal_type in property_enum.iter().map(ts_literal_type) {
+            if !literal_types.contains(&literal_type) {
+                literal_types.push(literal_type);
+            }
+        }
+        return literal_types.join(" | ");
+    }
+    // 用户类型映射
+    if let Some(ts_type) = &schema.ts_type {
+        return ts_type.clone();
+    }
+    if let Some(prefix_items) = &schema.prefix_items {
+        let mut item_types: Vec<String> = prefix_items
+            .iter()
+            .map(|v| open_3_get_type_name_from_schema(v, namespace.clone()))
+            .collect();
+        // 声明了 items 时，items 作为元组剩余元素的类型
+        if let Some(items) = &schema.items {
+            item_types.push(format!(
+                "...Array<{}>",
+                open_3_get_type_name_from_schema(items, namespace.clone())
+            ));
+        }
+        let tuple_type = format!("[{}]", item_types.join(", "));
+        return if schema.schema_type.as_ref().is_some_and(|v| v.contains("null")) {
+            format!("{} | null", tuple_type)
+        } else {
+            tuple_type
+        };
+    }
+    // 匿名对象生成对象字面量类型
+    if schema.properties.is_some() {
+        return open_3_get_object_literal_type(schema, namespace, false);
+    }
+    // 只声明了 additionalProperties 的对象生成 Record
+    if let Some(additional_properties) = &schema.additional_properties {
+        let value_type = open_3_get_additional_properties_type(additional_properties, namespace)
+            .unwrap_or_else(|| String::from("never"));
+        return format!("Record<string, {}>", value_type);
+    }
+    if let Some(schema_type) = &schema.schema_type {
+        let mut type_names: Vec<String> = vec![];
+        for schema_type in schema_type.types() {
+            let type_name = if schema_type.eq("array") {
+                let item_type = if let Some(items) = &schema.items {
+                    open_3_get_type_name_from_schema(items, namespace.clone())
+                } else {
+                    String::from("any")
+                };
+                format!("Array<{}>", item_type)
+            } else {
+                ts_type_transform(schema_type)
+            };
+            if !type_names.contains(&type_name) {
+                type_names.push(type_name);
+            }
+        }
+        if !type_names.is_empty() {
+            return type_names.join(" | ");
+        }
+    }
+    String::from("void")
+}
+
+/// 根据组合 schema 生成类型
+///
+/// allOf 生成交叉类型，oneOf、anyOf 生成联合类型，与 allOf 同级的 properties 作为交叉类型的一员
+///
+/// 如：allOf: [{$ref: Base}, {properties: {name: string}}]
+/// 则返回 Base & { "name"?: string }
+fn open_3_get_composition_type(
+    schema: &Open3Schema,
+    namespace: Option<String>,
+    ignore_option: bool,
+) -> String {
+    let get_member_type = |member: &Open3Schema| {
+        if member.schema_ref.is_none() && member.properties.is_some() && !member.is_composition() {
+            open_3_get_object_literal_type(member, namespace.clone(), ignore_option)
+        } else {
+            open_3_get_type_name_from_schema(member, namespace.clone())
+        }
+    };
+    let mut intersection_types: Vec<String> = vec![];
+    for member in schema.all_of.iter().flatten() {
+        intersection_types.push(get_member_type(member));
+    }
+    if schema.properties.is_some() {
+        intersection_types.push(open_3_get_object_literal_type(
+            schema,
+            namespace.clone(),
+            ignore_option,
+        ));
+    }
+    for union in [&schema.one_of, &schema.any_of].into_iter().flatten() {
+        let mut union_types: Vec<String> = vec![];
+        for union_type in union.iter().map(|member| {
+            // 存在 discriminator 时 将区分属性收窄为对应的字面量类型
+            match (&schema.discriminator, &member.schema_ref) {
+                (Some(discriminator), Some(schema_ref)) => format!(
+                    "{} & {{ {}: {} }}",
+                    get_member_type(member),
+                    ts_string_literal(&discriminator.property_name),
+                    open_3_get_discriminator_values(discriminator, schema_ref)
+                        .iter()
+                        .map(|v| ts_literal_type(&Value::String(v.to_string())))
+                        .collect::<Vec<String>>()
+                        .join(" | ")
+                ),
+                _ => get_member_type(member),
+            }
+        }) {
+            if !union_types.contains(&union_type) {
+                union_types.push(union_type);
+            }
+        }
+        if !union_types.is_empty() {
+            intersection_types.push(union_types.join(" | "));
+        }
+    }
+    intersection_types.retain(|v| !v.eq("void"));
+    if intersection_types.len() > 1 {
+        intersection_types = intersection_types
+            .into_iter()
+            .map(|v| if v.contains(" | ") { format!("({})", v) } else { v })
+            .collect();
+    }
+    if intersection_types.is_empty() {
+        return String::from("any");
+    }
+    intersection_types.join(" & ")
+}
+
+/// 获取 discriminator 中 schema 引用对应的属性值
+///
+/// mapping 中的值可以是完整的引用或 schema 名称，未在 mapping 中声明时使用 schema 名称
+fn open_3_get_discriminator_values(
+    discriminator: &Open3Discriminator,
+    schema_ref: &str,
+) -> Vec<String> {
+    let schema_name = get_schema_name_from_schema_ref(schema_ref);
+    let mut values: Vec<String> = discriminator
+        .mapping
+        .iter()
+        .flatten()
+        .filter(|(_, mapping_ref)| get_schema_name_from_schema_ref(mapping_ref).eq(&schema_name))
+        .map(|(value, _)| value.to_string())
+        .collect();
+    if values.is_empty() {
+        values.push(schema_name);
+    }
+    values.sort();
+    values
+}
+
+/// 生成 discriminator 联合类型的类型守卫函数
+///
+/// 如：Pet = Dog | Cat，区分属性为 petType
+/// 则生成 isPetDog(value: Pet): value is Extract<Pet, { "petType": "dog" }>
+///
+/// guard_name_vec 为已生成的函数名称，重名时按照 suffix_template 生成名称
+fn open_3_create_type_guard(
+    schema_name: &str,
+    components_schema: &Open3ComponentsSchema,
+    namespace: &Option<String>,
+    suffix_template: &str,
+    guard_name_vec: &mut Vec<String>,
+) -> Option<String> {
+    let discriminator = components_schema.discriminator.as_ref()?;
+    let union = components_schema
+        .one_of
+        .as_ref()
+        .or(components_schema.any_of.as_ref())?;
+    let union_name = open_3_get_type_name_from_schema_ref(
+        components_schema.title.as_deref().unwrap_or(schema_name),
+    );
+    let union_type = if let Some(namespace) = namespace {
+        format!("{}.{}", namespace, union_name)
     } else {
-        type_name
+        union_name.clone()
     };
-    let mut type_name_clone = type_name.clone();
-    if !type_name.eq("void") {
-        return Some((
-            type_name,
-            if !is_required {
-                type_name_clone.push_str(" | void");
-                type_name_clone
-            } else {
-                type_name_clone
-            },
+    let property_name = ts_literal_type(&Value::String(discriminator.property_name.clone()));
+    let mut guard_str = String::new();
+    for schema_ref in union.iter().filter_map(|v| v.schema_ref.as_ref()) {
+        let variant_name = open_3_get_type_name_from_schema_ref(schema_ref);
+        let values: Vec<String> = open_3_get_discriminator_values(discriminator, schema_ref)
+            .iter()
+            .map(|v| ts_literal_type(&Value::String(v.to_string())))
+            .collect();
+        let tag_type = values.join(" | ");
+        let condition = if values.len() == 1 {
+            format!("value[{property_name}] === {tag_type}")
+        } else {
+            format!(
+                "([{}] as unknown[]).includes(value[{property_name}])",
+                values.join(", ")
+            )
+        };
+        let guard_name = get_unique_name(
+            &format!("is{union_name}{variant_name}"),
+            suffix_template,
+            &|v| guard_name_vec.iter().any(|x| x.eq(v)),
+        );
+        guard_name_vec.push(guard_name.clone());
+        guard_str.push_str(&format!(
+            r#"
+/**
+ * 判断 {union_name} 是否是 {variant_name}
+ */
+export function {guard_name}(value: {union_type}): value is Extract<{union_type}, {{ {property_name}: {tag_type} }}> {{
+    return {condition};
+}}
+"#
         ));
     }
-    None
+    Some(guard_str)
 }
 
-/// 根据schema生成响应类型名称
+/// 将带有 properties 的匿名 schema 生成对象字面量类型
 ///
-/// 如果命令行参数指定了namesapce 则会将namespace拼接在类型前
-fn open_3_get_type_name_from_schema(
+/// 如：{ "name"?: string; "age": number }
+fn open_3_get_object_literal_type(
     schema: &Open3Schema,
     namespace: Option<String>,
-    generic: &str,
+    ignore_option: bool,
 ) -> String {
-    lazy_static! {
-        static ref SCHEMA_GENERIC_REGEX: Regex = Regex::new(r"<T>").unwrap();
+    let mut properties: Vec<(&String, &Open3Schema)> =
+        schema.properties.iter().flatten().collect();
+    properties.sort_by(|a, b| a.0.cmp(b.0));
+    let required_vec = schema.required.clone().unwrap_or_default();
+    let properties: Vec<String> = properties
+        .iter()
+        .map(|(property_name, property)| {
+            let property_option_split =
+                if ignore_option || required_vec.contains(property_name) {
+                    ""
+                } else {
+                    "?"
+                };
+            format!(
+                r#"{}{property_option_split}: {}"#,
+                ts_string_literal(property_name),
+                open_3_get_type_name_from_schema(property, namespace.clone())
+            )
+        })
+        .collect();
+    if properties.is_empty() && schema.additional_properties.is_none() {
+        return String::from("{ [key:string]:unknown }");
     }
-    if let Some(schema_ref) = &schema.schema_ref {
-        let schema_ref = open_3_get_type_name_from_schema_ref(schema_ref);
-        let schema_ref = if let Some(namespace) = namespace {
-            format!("{}.{}", namespace, schema_ref)
-        } else {
-            schema_ref
-        };
-        if generic.is_empty() {
-            return schema_ref;
+    let mut properties = properties;
+    if let Some(index_type) = open_3_get_index_signature_type(schema, namespace, ignore_option) {
+        properties.push(format!("[key:string]: {}", index_type));
+    }
+    format!("{{ {} }}", properties.join("; "))
+}
+
+/// 获取 additionalProperties 对应的值类型，为 false 时不允许额外属性 返回 None
+fn open_3_get_additional_properties_type(
+    additional_properties: &Open3AdditionalProperties,
+    namespace: Option<String>,
+) -> Option<String> {
+    match additional_properties {
+        Open3AdditionalProperties::Bool(true) => Some(String::from("unknown")),
+        Open3AdditionalProperties::Bool(false) => None,
+        // 空 schema 允许任意值
+        Open3AdditionalProperties::Schema(schema) => {
+            match open_3_get_type_name_from_schema(schema, namespace) {
+                value_type if value_type.eq("void") => Some(String::from("unknown")),
+                value_type => Some(value_type),
+            }
         }
-        return SCHEMA_GENERIC_REGEX
-            .replace_all(generic, format!("<{}>", schema_ref))
-            .to_string();
     }
-    if let Some(schema_type) = &schema.schema_type {
-        if schema_type.eq("array") {
-            let g = {
-                if generic.is_empty() {
-                    "Array<T>".to_string()
-                } else {
-                    SCHEMA_GENERIC_REGEX
-                        .replace_all(generic, format!("<{}>", "Array<T>"))
-                        .to_string()
-                }
-            };
-            return open_3_get_type_name_from_schema(schema.items.as_ref().unwrap(), namespace, &g);
+}
+
+/// 获取与已声明属性共存的索引签名类型
+///
+/// 索引签名的类型需要兼容所有已声明属性的类型，因此与属性类型合并为联合类型
+/// 如：properties: {name: string}, additionalProperties: {type: integer}
+/// 则返回 number | string | undefined
+fn open_3_get_index_signature_type(
+    schema: &Open3Schema,
+    namespace: Option<String>,
+    ignore_option: bool,
+) -> Option<String> {
+    let additional_properties = schema.additional_properties.as_ref()?;
+    let value_type = open_3_get_additional_properties_type(additional_properties, namespace.clone())?;
+    if value_type.eq("unknown") || value_type.eq("any") {
+        return Some(value_type);
+    }
+    let mut types = vec![value_type];
+    let required_vec = schema.required.clone().unwrap_or_default();
+    let mut properties: Vec<(&String, &Open3Schema)> =
+        schema.properties.iter().flatten().collect();
+    properties.sort_by(|a, b| a.0.cmp(b.0));
+    let mut has_optional = false;
+    for (property_name, property) in properties {
+        let property_type = open_3_get_type_name_from_schema(property, namespace.clone());
+        if !types.contains(&property_type) {
+            types.push(property_type);
+        }
+        has_optional |= !ignore_option && !required_vec.contains(property_name);
+    }
+    if has_optional {
+        types.push(String::from("undefined"));
+    }
+    if types.len() > 1 {
+        types = types
+            .into_iter()
+            .map(|v| if v.contains(" | ") || v.contains(" & ") { format!("({})", v) } else { v })
+            .collect();
+    }
+    Some(types.join(" | "))
+}
+
+/// 判断 components 中的 schema 是否需要生成类型别名
+///
+/// 没有 properties 且不是对象类型，如：枚举、数组、基本类型、引用其他 schema
+/// 未声明 type 的 schema 视为对象
+fn open_3_schema_is_alias(schema: &Open3Schema) -> bool {
+    if schema.properties.is_some() {
+        return false;
+    }
+    schema.schema_ref.is_some()
+        || schema.additional_properties.is_some()
+        || schema.const_value.is_some()
+        || schema.prefix_items.is_some()
+        || schema.property_enum.as_ref().is_some_and(|v| !v.is_empty())
+        || schema
+            .schema_type
+            .as_ref()
+            .is_some_and(|v| !v.contains("object") && !v.types().is_empty())
+}
+
+/// 生成枚举成员的说明文档
+///
+/// 使用 x-enum-descriptions 中的说明，如：- "ACTIVE": 启用
+fn open_3_get_enum_doc(schema: &Open3Schema, indent: &str) -> String {
+    let property_enum = schema
+        .items
+        .as_deref()
+        .filter(|_| schema.property_enum.is_none())
+        .unwrap_or(schema);
+    let Some(descriptions) = &property_enum.x_enum_descriptions else {
+        return String::new();
+    };
+    property_enum
+        .property_enum
+        .iter()
+        .flatten()
+        .zip(descriptions)
+        .map(|(value, description)| {
+            format!(
+                "\n{indent} * - {}: {}",
+                escape_doc_comment(&ts_literal_type(value), indent),
+                escape_doc_comment(description, indent)
+            )
+        })
+        .collect()
+}
+
+/// 生成运行时的枚举
+///
+/// 成员名称优先使用 x-enum-varnames，成员值不是字符串或数字时 enum 无法表示，使用 as const 对象
+fn open_3_create_enum(enum_name: &str, schema: &Open3Schema, enum_style: EnumStyle) -> Option<String> {
+    let property_enum = schema.property_enum.as_ref().filter(|v| !v.is_empty())?;
+    let is_enum = enum_style == EnumStyle::Enum
+        && property_enum.iter().all(|v| v.is_string() || v.is_number());
+    let varnames = schema.x_enum_varnames.clone().unwrap_or_default();
+    let descriptions = schema.x_enum_descriptions.clone().unwrap_or_default();
+    let mut member_names: Vec<String> = vec![];
+    let mut members = String::new();
+    for (index, value) in property_enum.iter().enumerate() {
+        let mut member_name = varnames
+            .get(index)
+            .map(|v| open_3_get_enum_member_name(v))
+            .unwrap_or_else(|| match value {
+                Value::String(v) => open_3_get_enum_member_name(v),
+                v => open_3_get_enum_member_name(&v.to_string()),
+            });
+        // 成员名称重复时添加序号
+        if member_names.contains(&member_name) {
+            member_name = format!("{}{}", member_name, index);
+        }
+        member_names.push(member_name.clone());
+        if let Some(description) = descriptions.get(index) {
+            members.push_str(&format!("\n    /** {} */", escape_doc_comment(description, "    ")));
         }
-        let translate_type = ts_type_transform(&schema_type.clone());
-        if generic.is_empty() {
-            return translate_type;
+        let value = ts_literal_type(value);
+        if is_enum {
+            members.push_str(&format!("\n    {member_name} = {value},"));
+        } else {
+            members.push_str(&format!("\n    {member_name}: {value},"));
         }
-        return SCHEMA_GENERIC_REGEX
-            .replace_all(generic, format!("<{}>", translate_type))
-            .to_string();
     }
-    String::from("void")
+    let description = escape_doc_comment(schema.description.as_deref().unwrap_or_default(), "");
+    Some(if is_enum {
+        format!(
+            r#"
+/**
+ * {description}
+ */
+export enum {enum_name} {{{members}
+}}
+"#
+        )
+    } else {
+        format!(
+            r#"
+/**
+ * {description}
+ */
+export const {enum_name} = {{{members}
+}} as const;
+export type {enum_name} = (typeof {enum_name})[keyof typeof {enum_name}];
+"#
+        )
+    })
+}
+
+/// 生成枚举成员名称
+///
+/// 合法的标识符保持不变，否则转换为大驼峰，如：in-progress 则返回 InProgress，数字开头时添加 _ 前缀
+fn open_3_get_enum_member_name(value: &str) -> String {
+    let is_identifier = value.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$')
+        && value.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        return value.to_string();
+    }
+    // 数字值 如：-1.5 则返回 _Minus1_5
+    if value.parse::<f64>().is_ok() {
+        return format!("_{}", value.replace('-', "Minus").replace(['.', '+'], "_"));
+    }
+    let member_name: String = value
+        .trim()
+        .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .map(capitalize)
+        .collect();
+    if member_name.is_empty() || member_name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", member_name)
+    } else {
+        member_name
+    }
+}
+
+/// 将 json 值转换为 ts 的字面量类型
+///
+/// json 的字面量写法与 ts 的字面量类型写法一致
+fn ts_literal_type(value: &Value) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| String::from("any"))
 }
 
 /// 根据schema ref获取schema名称
@@ -436,13 +2541,7 @@ fn get_schema_name_from_schema_ref(schema_ref: &str) -> String {
 /// 如：#/components/schemas/Result«User»
 /// 则返回 ResultUser
 fn open_3_get_type_name_from_schema_ref(schema_ref: &str) -> String {
-    lazy_static! {
-        static ref SCHEMA_TYPE_NAME_REGEX: Regex = Regex::new(r"[«»,-]").unwrap();
-    }
-    let schema_name = get_schema_name_from_schema_ref(schema_ref);
-    SCHEMA_TYPE_NAME_REGEX
-        .replace_all(&schema_name, "")
-        .to_string()
+    ts_type_identifier(&get_schema_name_from_schema_ref(schema_ref))
 }
 
 /// 判断 schema 参数是否是必须的
@@ -461,20 +2560,24 @@ fn open_3_schema_is_required(components: &Open3Components, scheme_ref: &str) ->
 /// 生成typescript interface类型
 fn open_3_create_ts_interface_enum(
     components_schema: &Open3ComponentsSchema,
-    request_type_name_vec: &Vec<String>,
+    request_type_name_vec: &[String],
     namespace: &Option<String>,
     ignore_option: &bool,
 ) -> String {
     let interface_name =
         open_3_get_type_name_from_schema_ref(components_schema.title.as_ref().unwrap());
-    let mut interface_str = format!("interface {} {{", &interface_name);
+    let mut interface_str = if let Some(type_parameters) = &components_schema.type_parameters {
+        format!("interface {}<{}> {{", &interface_name, type_parameters.join(", "))
+    } else {
+        format!("interface {} {{", &interface_name)
+    };
     let mut open_api_schema_vec: Vec<(&String, &Open3Schema)> =
         if let Some(properties) = &components_schema.properties {
-            properties.into_iter().collect()
+            properties.iter().collect()
         } else {
             vec![]
         };
-    open_api_schema_vec.sort_by(|a, b| a.0.cmp(&b.0));
+    open_api_schema_vec.sort_by(|a, b| a.0.cmp(b.0));
     let required_default_vec = vec![];
     let required_vec: &Vec<String> = if let Some(required_vec) = &components_schema.required {
         required_vec
@@ -488,31 +2591,63 @@ fn open_3_create_ts_interface_enum(
     };
     let is_request_name_interface = request_type_name_vec.contains(&interface_with_namespace);
     let ignore_option = !is_request_name_interface && *ignore_option;
+    // 枚举、数组、基本类型以及引用生成类型别名
+    if open_3_schema_is_alias(components_schema) {
+        let description =
+            escape_doc_comment(components_schema.description.as_deref().unwrap_or_default(), "");
+        let enum_doc = open_3_get_enum_doc(components_schema, "");
+        let enum_type = open_3_get_type_name_from_schema(components_schema, None);
+        return format!(
+            r#"/**
+ * {description}{enum_doc}
+ */
+type {interface_name} = {enum_type};
+
+"#
+        );
+    }
+    // 组合类型生成类型别名
+    if components_schema.is_composition() {
+        let composition_type = open_3_get_composition_type(components_schema, None, ignore_option);
+        return format!("type {interface_name} = {composition_type};\n\n");
+    }
     for (property_name, property) in open_api_schema_vec.iter() {
         let property_option_split = if ignore_option || required_vec.contains(property_name) {
             ""
         } else {
             "?"
         };
-        let schema_type = open_3_get_type_name_from_schema(property, None, "");
-        let description = if let Some(description) = &property.description {
-            description
-        } else {
-            ""
-        };
+        let schema_type = open_3_get_type_name_from_schema(property, None);
+        let description = escape_doc_comment(property.description.as_deref().unwrap_or_default(), "  ");
+        // 示例值 3.1 中使用 examples 数组
+        let example_doc: String = property
+            .example
+            .iter()
+            .chain(property.examples.iter().flatten())
+            .map(|v| format!("\n   * @example {}", escape_doc_comment(&v.to_string(), "  ")))
+            .collect();
+        let enum_doc = open_3_get_enum_doc(property, "  ");
+        let type_doc = escape_doc_comment(&schema_type, "  ");
+        let property_key = ts_string_literal(property_name);
         let interface_item = format!(
             r#"
   /**
-   * {description}
-   * @type {schema_type}
+   * {description}{enum_doc}{example_doc}
+   * @type {type_doc}
    * @memberof {interface_name}
    */
-  "{property_name}"{property_option_split}: {schema_type};"#
+  {property_key}{property_option_split}: {schema_type};"#
         );
         interface_str.push_str(&interface_item);
     }
-    // 对于空的interface，添加string unknown的签名
-    if open_api_schema_vec.is_empty() {
+    // additionalProperties 生成索引签名
+    if let Some(index_type) = open_3_get_index_signature_type(components_schema, None, ignore_option) {
+        interface_str.push_str(&format!(
+            r"
+  [key:string]: {index_type};"
+        ));
+    } else if open_api_schema_vec.is_empty() {
+        // 对于空的interface，添加string unknown的签名
         interface_str.push_str(
             r"
   [key:string]:unknown;",
@@ -521,3 +2656,771 @@ fn open_3_create_ts_interface_enum(
     interface_str.push_str("\n}\n\n");
     interface_str
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::tools::http_request::HttpConfig;
+
+    fn components(schemas: Value) -> Open3Components {
+        serde_json::from_value(json!({ "schemas": schemas })).unwrap()
+    }
+
+    /// 与命令行默认值一致的配置
+    fn command_config() -> CommandConfig {
+        CommandConfig {
+            workspace: String::from("output"),
+            split: false,
+            open_config_path: String::from("openapi.json"),
+            controller_dir_name: String::from("module"),
+            ignore_option: false,
+            tags: vec![],
+            operation_prefix: None,
+            namespace: None,
+            wrap: None,
+            http: HttpConfig::default(),
+            enum_style: EnumStyle::Union,
+            inline_object: InlineObjectStyle::Hoist,
+            type_mapping: HashMap::new(),
+            flatten_generics: false,
+            name_collision_suffix: String::from("{name}{index}"),
+            default_module: String::from("default"),
+            multi_tag: MultiTagStyle::First,
+            module_naming: ModuleNaming::Description,
+            module_name_map: HashMap::new(),
+            all_schemas: false,
+        }
+    }
+
+    fn get_operation_ids(config: &Open3Config) -> Vec<(String, String)> {
+        let mut operation_ids: Vec<(String, String)> = config
+            .paths
+            .iter()
+            .flat_map(|(url, requests)| {
+                requests.iter().filter_map(move |(method, v)| {
+                    v.as_ref()
+                        .map(|v| (format!("{} {}", method, url), v.operation_id.clone()))
+                })
+            })
+            .collect();
+        operation_ids.sort();
+        operation_ids
+    }
+
+    #[test]
+    fn converter_name_collision_test() {
+        let mut components = components(json!({
+            "UserDto": { "type": "object", "properties": { "id": { "$ref": "#/components/schemas/Id" } } },
+            "User.Dto": { "type": "object", "properties": { "id": { "$ref": "#/components/schemas/Id" } } },
+            "Id": { "type": "integer", "format": "int64" }
+        }));
+        // 类型映射后的 int64 需要转换为 bigint
+        components.schemas.get_mut("Id").unwrap().ts_convert = Some(String::from("BigInt(value)"));
+        let converted = open_3_get_converted_schemas(&components, "{name}_{index}");
+        assert_eq!(converted["Id"], "convertId");
+        assert_eq!(converted["User.Dto"], "convertUserDto");
+        assert_eq!(converted["UserDto"], "convertUserDto_2");
+    }
+
+    #[test]
+    fn type_guard_name_collision_test() {
+        let components = components(json!({
+            "Pet": {
+                "oneOf": [{ "$ref": "#/components/schemas/DogCat" }],
+                "discriminator": { "propertyName": "kind" }
+            },
+            "PetDog": {
+                "oneOf": [{ "$ref": "#/components/schemas/Cat" }],
+                "discriminator": { "propertyName": "kind" }
+            }
+        }));
+        let mut guard_name_vec = vec![];
+        for schema_name in ["Pet", "PetDog"] {
+            open_3_create_type_guard(
+                schema_name,
+                &components.schemas[schema_name],
+                &None,
+                "{name}{index}",
+                &mut guard_name_vec,
+            )
+            .unwrap();
+        }
+        assert_eq!(guard_name_vec, vec!["isPetDogCat", "isPetDogCat2"]);
+    }
+
+    /// 生成 components 中指定 schema 的声明
+    fn interface_declaration(components: &mut Open3Components, schema_name: &str) -> String {
+        let schema = components.schemas.get_mut(schema_name).unwrap();
+        schema.title = Some(schema_name.to_string());
+        open_3_create_ts_interface_enum(schema, &[], &None, &false)
+    }
+
+    #[test]
+    fn composition_type_test() {
+        let mut components = components(json!({
+            "Base": { "type": "object", "properties": { "id": { "type": "integer" } } },
+            "Dog": { "type": "object", "properties": { "bark": { "type": "boolean" } } },
+            "Cat": { "type": "object", "properties": { "lives": { "type": "integer" } } },
+            "Named": {
+                "allOf": [
+                    { "$ref": "#/components/schemas/Base" },
+                    { "type": "object", "properties": { "name": { "type": "string" } } }
+                ]
+            },
+            "Pet": {
+                "oneOf": [
+                    { "$ref": "#/components/schemas/Dog" },
+                    { "$ref": "#/components/schemas/Cat" }
+                ]
+            },
+            "AnyPet": {
+                "allOf": [{ "$ref": "#/components/schemas/Base" }],
+                "anyOf": [
+                    { "$ref": "#/components/schemas/Dog" },
+                    { "$ref": "#/components/schemas/Cat" }
+                ]
+            }
+        }));
+        assert_eq!(
+            interface_declaration(&mut components, "Named").trim(),
+            r#"type Named = Base & { "name"?: string };"#
+        );
+        assert_eq!(
+            interface_declaration(&mut components, "Pet").trim(),
+            "type Pet = Dog | Cat;"
+        );
+        // 与 anyOf 同级的 allOf 作为交叉类型的一员，联合类型需要加括号
+        assert_eq!(
+            interface_declaration(&mut components, "AnyPet").trim(),
+            "type AnyPet = Base & (Dog | Cat);"
+        );
+    }
+
+    #[test]
+    fn discriminated_union_test() {
+        let mut components = components(json!({
+            "Dog": { "type": "object", "properties": { "pet-type": { "type": "string" } } },
+            "Cat": { "type": "object", "properties": { "pet-type": { "type": "string" } } },
+            "Pet": {
+                "oneOf": [
+                    { "$ref": "#/components/schemas/Dog" },
+                    { "$ref": "#/components/schemas/Cat" }
+                ],
+                "discriminator": {
+                    "propertyName": "pet-type",
+                    "mapping": {
+                        "dog": "#/components/schemas/Dog",
+                        "puppy": "#/components/schemas/Dog",
+                        "cat": "Cat"
+                    }
+                }
+            }
+        }));
+        assert_eq!(
+            interface_declaration(&mut components, "Pet").trim(),
+            r#"type Pet = Dog & { "pet-type": "dog" | "puppy" } | Cat & { "pet-type": "cat" };"#
+        );
+        let guard = open_3_create_type_guard(
+            "Pet",
+            &components.schemas["Pet"],
+            &None,
+            "{name}{index}",
+            &mut vec![],
+        )
+        .unwrap();
+        assert!(guard.contains(
+            r#"export function isPetDog(value: Pet): value is Extract<Pet, { "pet-type": "dog" | "puppy" }> {
+    return (["dog", "puppy"] as unknown[]).includes(value["pet-type"]);
+}"#
+        ));
+        assert!(guard.contains(
+            r#"export function isPetCat(value: Pet): value is Extract<Pet, { "pet-type": "cat" }> {
+    return value["pet-type"] === "cat";
+}"#
+        ));
+    }
+
+    #[test]
+    fn hoist_defs_test() {
+        let mut config: Open3Config = serde_json::from_value(json!({
+            "components": {
+                "schemas": {
+                    "Address": { "type": "object", "properties": { "id": { "type": "integer" } } },
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "address": { "$ref": "#/components/schemas/User/$defs/Address" }
+                        },
+                        "$defs": {
+                            "Address": {
+                                "type": "object",
+                                "properties": {
+                                    "street": {
+                                        "$ref": "#/components/schemas/User/$defs/Address/$defs/Street"
+                                    }
+                                },
+                                "$defs": {
+                                    "Street": { "type": "string" }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "paths": {}
+        }))
+        .unwrap();
+        open_3_hoist_defs(&mut config, "{name}{index}");
+        let schemas = &config.components.schemas;
+        let mut schema_names: Vec<&String> = schemas.keys().collect();
+        schema_names.sort();
+        // 与已有 schema 重名的 $defs 重命名，嵌套的 $defs 同样提升
+        assert_eq!(schema_names, vec!["Address", "Address2", "Street", "User"]);
+        assert!(schemas.values().all(|v| v.defs.is_none()));
+        assert_eq!(
+            schemas["User"].properties.as_ref().unwrap()["address"].schema_ref,
+            Some(String::from("#/components/schemas/Address2"))
+        );
+        assert_eq!(
+            schemas["Address2"].properties.as_ref().unwrap()["street"].schema_ref,
+            Some(String::from(
+                "#/components/schemas/User/$defs/Address/$defs/Street"
+            ))
+        );
+        // 未重命名的 $defs 引用按最后一段名称查找类型
+        assert_eq!(
+            open_3_get_type_name_from_schema_ref(
+                "#/components/schemas/User/$defs/Address/$defs/Street"
+            ),
+            "Street"
+        );
+        assert!(schemas["Address"]
+            .properties
+            .as_ref()
+            .unwrap()
+            .contains_key("id"));
+    }
+
+    #[test]
+    fn fill_operation_names_test() {
+        let response = json!({ "200": { "description": "ok" } });
+        let mut config: Open3Config = serde_json::from_value(json!({
+            "components": { "schemas": {} },
+            "paths": {
+                "/users": {
+                    "get": { "responses": response },
+                    "post": { "operationId": "get-users", "responses": response },
+                    "put": { "operationId": "getUsers", "responses": response }
+                },
+                "/users/{user-id}": {
+                    "delete": { "operationId": " ", "responses": response }
+                }
+            }
+        }))
+        .unwrap();
+        open_3_fill_operation_names(&mut config, &command_config());
+        // 转换为标识符后重名的 operationId 以及生成的名称都需要重命名
+        assert_eq!(
+            get_operation_ids(&config),
+            vec![
+                (
+                    String::from("delete /users/{user-id}"),
+                    String::from("deleteUsersByUserId")
+                ),
+                (String::from("get /users"), String::from("getUsers3")),
+                (String::from("post /users"), String::from("get-users")),
+                (String::from("put /users"), String::from("getUsers2")),
+            ]
+        );
+        assert!(config.paths["/users"]
+            .get
+            .as_ref()
+            .unwrap()
+            .tags
+            .eq(&vec![String::from("default")]));
+    }
+
+    #[test]
+    fn fill_operation_names_with_prefix_test() {
+        let response = json!({ "200": { "description": "ok" } });
+        let mut config: Open3Config = serde_json::from_value(json!({
+            "components": { "schemas": {} },
+            "paths": {
+                "/users": {
+                    "post": { "operationId": "-getUsers", "responses": response },
+                    "put": { "operationId": "GetUsers", "responses": response }
+                }
+            }
+        }))
+        .unwrap();
+        let mut command_config = command_config();
+        command_config.operation_prefix = Some(String::from("Api"));
+        open_3_fill_operation_names(&mut config, &command_config);
+        // 加上前缀后 Api-getUsers 与 ApiGetUsers 都转换为 ApiGetUsers
+        assert_eq!(
+            get_operation_ids(&config),
+            vec![
+                (String::from("post /users"), String::from("-getUsers")),
+                (String::from("put /users"), String::from("GetUsers2")),
+            ]
+        );
+    }
+
+    /// 两个模块的接口分别引用 User 和 Order，User 引用 Address
+    fn reachable_config() -> Open3Config {
+        let response = |schema_name: &str| {
+            json!({
+                "200": {
+                    "description": "ok",
+                    "content": {
+                        "application/json": {
+                            "schema": { "$ref": format!("#/components/schemas/{}", schema_name) }
+                        }
+                    }
+                }
+            })
+        };
+        serde_json::from_value(json!({
+            "paths": {
+                "/users": {
+                    "get": { "operationId": "getUser", "tags": ["user"], "responses": response("User") }
+                },
+                "/orders": {
+                    "get": { "operationId": "getOrder", "tags": ["order"], "responses": response("Order") }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "address": { "$ref": "#/components/schemas/Address" },
+                            "createdAt": { "type": "string", "format": "date-time" }
+                        }
+                    },
+                    "Address": { "type": "object", "properties": { "city": { "type": "string" } } },
+                    "Order": {
+                        "type": "object",
+                        "properties": { "createdAt": { "type": "string", "format": "date-time" } }
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn reachable_schemas_test() {
+        let mut config = reachable_config();
+        let mut command_config = command_config();
+        command_config.tags = vec![String::from("user")];
+        let parser = OpenApi3JavaScript::new(&mut config, &command_config);
+        assert!(parser.is_schema_reachable("User"));
+        assert!(parser.is_schema_reachable("Address"));
+        assert!(!parser.is_schema_reachable("Order"));
+
+        // --all-schemas 时生成所有 schema
+        let mut config = reachable_config();
+        command_config.all_schemas = true;
+        let parser = OpenApi3JavaScript::new(&mut config, &command_config);
+        assert!(parser.is_schema_reachable("Order"));
+    }
+
+    #[test]
+    fn reachable_converter_list_test() {
+        let mut config = reachable_config();
+        let mut command_config = command_config();
+        command_config.tags = vec![String::from("user")];
+        command_config.type_mapping = HashMap::from([(
+            String::from("string/date-time"),
+            TypeMapping::Type(String::from("Date")),
+        )]);
+        let parser = OpenApi3JavaScript::new(&mut config, &command_config);
+        let converter_list = parser.get_converter_list().join("");
+        assert!(converter_list.contains("function convertUser("));
+        assert!(!converter_list.contains("function convertOrder("));
+    }
+
+    #[test]
+    fn ref_schema_names_test() {
+        let mut schema_names =
+            open_3_get_ref_schema_names("#/components/schemas/Result«List«User»»");
+        schema_names.sort();
+        assert_eq!(
+            schema_names,
+            vec!["List", "Result", "Result«List«User»»", "User"]
+        );
+    }
+
+    #[test]
+    fn json_body_with_parameters_is_not_form_test() {
+        let mut config: Open3Config = serde_json::from_value(json!({
+            "paths": {
+                "/users/{id}/orders": {
+                    "parameters": [
+                        { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }
+                    ],
+                    "post": {
+                        "operationId": "createUserOrder",
+                        "tags": ["order"],
+                        "parameters": [{ "name": "notify", "in": "query", "schema": { "type": "boolean" } }],
+                        "requestBody": {
+                            "required": true,
+                            "content": {
+                                "application/json": { "schema": { "$ref": "#/components/schemas/Order" } }
+                            }
+                        },
+                        "responses": { "200": { "description": "ok" } }
+                    }
+                },
+                "/avatars": {
+                    "post": {
+                        "operationId": "uploadAvatar",
+                        "tags": ["order"],
+                        "requestBody": {
+                            "content": {
+                                "multipart/form-data": {
+                                    "schema": { "type": "object", "properties": { "file": { "type": "string", "format": "binary" } } }
+                                }
+                            }
+                        },
+                        "responses": { "200": { "description": "ok" } }
+                    }
+                }
+            },
+            "components": {
+                "schemas": { "Order": { "type": "object", "properties": { "count": { "type": "integer" } } } }
+            }
+        }))
+        .unwrap();
+        let command_config = command_config();
+        let parser = OpenApi3JavaScript::new(&mut config, &command_config);
+        let get_requester = |operation_id: &str| {
+            parser
+                .get_api_list()
+                .iter()
+                .map(|v| &v.1)
+                .find(|v| v.operation_id.eq(operation_id))
+                .unwrap()
+        };
+        let requester = get_requester("createUserOrder");
+        assert!(!requester.is_form);
+        assert_eq!(requester.request_schema_name, "Order");
+        assert_eq!(
+            requester.parameters_type_name.as_deref(),
+            Some("CreateUserOrderQuery")
+        );
+        assert!(get_requester("uploadAvatar").is_form);
+    }
+
+    #[test]
+    fn operation_type_name_test() {
+        assert_eq!(
+            open_3_get_operation_type_name("get-user", "Query"),
+            "GetUserQuery"
+        );
+        assert_eq!(
+            open_3_get_operation_type_name("2fa-verify", "Query"),
+            "_2faVerifyQuery"
+        );
+        assert_eq!(
+            open_3_get_operation_type_name("delete", "Params"),
+            "DeleteParams"
+        );
+    }
+
+    #[test]
+    fn operation_type_declaration_test() {
+        let mut config: Open3Config = serde_json::from_value(json!({
+            "paths": {
+                "/verify": {
+                    "get": {
+                        "operationId": "2fa-verify",
+                        "tags": ["auth"],
+                        "parameters": [
+                            { "name": "code", "in": "query", "required": true, "schema": { "type": "string" } }
+                        ],
+                        "responses": { "200": { "description": "ok" } }
+                    }
+                },
+                "/items/{id}": {
+                    "post": {
+                        "operationId": "delete",
+                        "tags": ["auth"],
+                        "parameters": [
+                            { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }
+                        ],
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": { "type": "object", "properties": { "name": { "type": "string" } } }
+                                }
+                            }
+                        },
+                        "responses": { "200": { "description": "ok" } }
+                    }
+                }
+            },
+            "components": { "schemas": {} }
+        }))
+        .unwrap();
+        let command_config = command_config();
+        let mut parser = OpenApi3JavaScript::new(&mut config, &command_config);
+        let api_list: Vec<OpenApiRequester> =
+            parser.get_api_list().iter().map(|v| v.1.clone()).collect();
+        let declarations = parser.get_interface_enum_list(&false).join("");
+        // 声明与使用处的类型名称一致
+        let verify = api_list
+            .iter()
+            .find(|v| v.operation_id.eq("2fa-verify"))
+            .unwrap();
+        assert_eq!(verify.request_type_name, "_2faVerifyQuery");
+        assert!(declarations.contains("interface _2faVerifyQuery {"));
+        let delete = api_list
+            .iter()
+            .find(|v| v.operation_id.eq("delete"))
+            .unwrap();
+        assert_eq!(delete.request_schema_name, "DeleteParams");
+        assert_eq!(delete.parameters_type_name.as_deref(), Some("DeleteQuery"));
+        assert!(declarations.contains("interface DeleteParams {"));
+        assert!(declarations.contains("interface DeleteQuery {"));
+    }
+
+    /// 请求体与响应都引用 schema_name 的文档
+    fn read_write_config(schema_name: &str, schemas: Value) -> Open3Config {
+        let content = json!({
+            "application/json": {
+                "schema": { "$ref": format!("#/components/schemas/{}", schema_name) }
+            }
+        });
+        serde_json::from_value(json!({
+            "paths": {
+                "/users": {
+                    "post": {
+                        "operationId": "createUser",
+                        "requestBody": { "content": content },
+                        "responses": { "200": { "description": "ok", "content": content } }
+                    }
+                }
+            },
+            "components": { "schemas": schemas }
+        }))
+        .unwrap()
+    }
+
+    fn get_property_names(config: &Open3Config, schema_name: &str) -> Vec<String> {
+        let mut property_names: Vec<String> = config.components.schemas[schema_name]
+            .properties
+            .iter()
+            .flatten()
+            .map(|v| v.0.clone())
+            .collect();
+        property_names.sort();
+        property_names
+    }
+
+    fn get_body_refs(config: &Open3Config) -> (Option<String>, Option<String>) {
+        let api_config = config.paths["/users"].post.as_ref().unwrap();
+        let request_ref = api_config
+            .request_body
+            .as_ref()
+            .and_then(|v| v.get_content("application/json"))
+            .and_then(|v| v.schema.schema_ref.clone());
+        let response_ref =
+            open_3_get_response_schema(api_config).and_then(|v| v.schema_ref.clone());
+        (request_ref, response_ref)
+    }
+
+    #[test]
+    fn split_read_write_schemas_test() {
+        let mut config = read_write_config(
+            "User",
+            json!({
+                "User": {
+                    "type": "object",
+                    "required": ["id", "name"],
+                    "properties": {
+                        "id": { "type": "integer", "readOnly": true },
+                        "name": { "type": "string" },
+                        "password": { "type": "string", "writeOnly": true },
+                        "manager": { "$ref": "#/components/schemas/User" }
+                    }
+                }
+            }),
+        );
+        open_3_split_read_write_schemas(&mut config, "{name}{index}");
+        assert_eq!(
+            get_body_refs(&config),
+            (
+                Some(String::from("#/components/schemas/UserRequest")),
+                Some(String::from("#/components/schemas/UserResponse"))
+            )
+        );
+        assert_eq!(
+            get_property_names(&config, "UserRequest"),
+            vec!["manager", "name", "password"]
+        );
+        assert_eq!(
+            get_property_names(&config, "UserResponse"),
+            vec!["id", "manager", "name"]
+        );
+        // 去除的属性同时从 required 中去除，自引用指向拆分后的 schema
+        let request_schema = &config.components.schemas["UserRequest"];
+        assert_eq!(request_schema.required, Some(vec![String::from("name")]));
+        assert_eq!(
+            request_schema.properties.as_ref().unwrap()["manager"]
+                .schema_ref
+                .as_deref(),
+            Some("#/components/schemas/UserRequest")
+        );
+        // 原 schema 保持不变
+        assert_eq!(
+            get_property_names(&config, "User"),
+            vec!["id", "manager", "name", "password"]
+        );
+    }
+
+    #[test]
+    fn split_read_only_schemas_test() {
+        let mut config = read_write_config(
+            "User",
+            json!({
+                "User": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "integer", "readOnly": true },
+                        "name": { "type": "string" }
+                    }
+                }
+            }),
+        );
+        open_3_split_read_write_schemas(&mut config, "{name}{index}");
+        // 只有 readOnly 属性时 响应使用原 schema
+        assert_eq!(
+            get_body_refs(&config),
+            (
+                Some(String::from("#/components/schemas/UserRequest")),
+                Some(String::from("#/components/schemas/User"))
+            )
+        );
+        assert_eq!(get_property_names(&config, "UserRequest"), vec!["name"]);
+        assert!(!config.components.schemas.contains_key("UserResponse"));
+    }
+
+    #[test]
+    fn split_schema_name_collision_test() {
+        let mut config = read_write_config(
+            "User",
+            json!({
+                "User": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "integer", "readOnly": true },
+                        "password": { "type": "string", "writeOnly": true }
+                    }
+                },
+                "UserRequest": { "type": "object", "properties": { "page": { "type": "integer" } } },
+                "UserResponse": { "type": "object", "properties": { "total": { "type": "integer" } } }
+            }),
+        );
+        open_3_split_read_write_schemas(&mut config, "{name}_{index}");
+        assert_eq!(
+            get_body_refs(&config),
+            (
+                Some(String::from("#/components/schemas/UserRequest_2")),
+                Some(String::from("#/components/schemas/UserResponse_2"))
+            )
+        );
+        assert_eq!(get_property_names(&config, "UserRequest"), vec!["page"]);
+        assert_eq!(
+            get_property_names(&config, "UserRequest_2"),
+            vec!["password"]
+        );
+        assert_eq!(get_property_names(&config, "UserResponse_2"), vec!["id"]);
+    }
+
+    #[test]
+    fn hoist_before_split_test() {
+        let mut config = read_write_config(
+            "User",
+            json!({
+                "User": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "integer", "readOnly": true },
+                        "address": {
+                            "type": "object",
+                            "nullable": true,
+                            "properties": { "city": { "type": "string" } }
+                        }
+                    }
+                }
+            }),
+        );
+        let command_config = command_config();
+        OpenApi3JavaScript::new(&mut config, &command_config);
+        // 匿名对象只提升一次，可为空保留在引用的属性上
+        let schemas = &config.components.schemas;
+        assert!(schemas.contains_key("UserAddress"));
+        assert!(!schemas.contains_key("UserRequestAddress"));
+        let address = &schemas["UserRequest"].properties.as_ref().unwrap()["address"];
+        assert_eq!(
+            address.schema_ref.as_deref(),
+            Some("#/components/schemas/UserAddress")
+        );
+        assert_eq!(address.nullable, Some(true));
+        assert_eq!(schemas["UserAddress"].nullable, None);
+    }
+
+    #[test]
+    fn type_name_is_taken_test() {
+        let components = components(json!({
+            "User-Info": { "type": "object" },
+            "Pet": { "type": "object", "title": "Animal" }
+        }));
+        assert!(open_3_type_name_is_taken(&components, "UserInfo"));
+        assert!(open_3_type_name_is_taken(&components, "User.Info"));
+        assert!(open_3_type_name_is_taken(&components, "Pet"));
+        assert!(open_3_type_name_is_taken(&components, "Animal"));
+        assert!(!open_3_type_name_is_taken(&components, "User"));
+    }
+
+    #[test]
+    fn hoisted_name_collision_test() {
+        let mut components = components(json!({
+            "User-Address": { "type": "object", "properties": { "id": { "type": "integer" } } },
+            "WeirdName": {
+                "type": "object",
+                "properties": {
+                    "my-prop": { "type": "object", "properties": { "a": { "type": "string" } } }
+                }
+            },
+            "User": {
+                "type": "object",
+                "properties": {
+                    "address": { "type": "object", "properties": { "city": { "type": "string" } } }
+                }
+            }
+        }));
+        open_3_hoist_inline_objects(&mut components, "{name}{index}");
+        // 转换为类型名称后与 User-Address 重名
+        assert_eq!(
+            components.schemas["User"].properties.as_ref().unwrap()["address"]
+                .schema_ref
+                .as_deref(),
+            Some("#/components/schemas/UserAddress2")
+        );
+        assert!(components.schemas.contains_key("WeirdNameMyProp"));
+    }
+
+    #[test]
+    fn generated_type_name_collision_test() {
+        let components = components(json!({ "Get-UserQuery": { "type": "object" } }));
+        assert_eq!(
+            open_3_get_generated_type_name(&components, "GetUserQuery", "{name}{index}"),
+            "GetUserQuery2"
+        );
+    }
+}
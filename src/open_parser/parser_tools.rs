@@ -10,7 +10,7 @@ pub struct OpenApiRequester {
     /// 接口调用地址
     pub url: String,
 
-    /// 请求方式 目前支持 get post delete put
+    /// 请求方式 支持 get post put delete patch head options trace
     pub method: String,
 
     pub request_schema_name: String,
@@ -25,16 +25,49 @@ pub struct OpenApiRequester {
     pub response_type_name: String,
 
     /// 判断是否是form
-    /// post请求， 有parameter则视为form
-    pub is_form: bool
+    /// post请求， requestBody为表单类型则视为form
+    pub is_form: bool,
+
+    /// 请求参数，用于生成请求地址以及区分 query、header、cookie 参数
+    ///
+    /// get 等请求中参数包含在 request_type_name 中，
+    /// post 等有请求体的请求中参数包含在 parameters_type_name 中
+    pub parameters: Vec<OpenApiParameter>,
+
+    pub parameters_schema_name: Option<String>,
+
+    /// 有请求体的请求中 地址、query、header、cookie 参数的类型
+    pub parameters_type_name: Option<String>,
+
+    /// 响应值的转换表达式，使用 res 表示响应值，不需要转换时为 None
+    pub response_converter: Option<String>,
+
+    /// 请求体中包含 bigint，发送前需要转换为 JSON.stringify 可以序列化的值
+    pub serialize_body: bool,
+
+    /// 多个 tag 的接口使用 reexport 方式时，从该模块重新导出，不再生成接口调用
+    pub reexport_module: Option<String>,
 }
 
+#[derive(Debug, Clone)]
+pub struct OpenApiParameter {
+    /// 参数名称
+    pub name: String,
+
+    /// 参数位置 path query header cookie
+    pub parameter_in: String,
+}
+
+#[derive(Debug, Clone)]
 pub struct OpenApiModule {
     // tag 描述
     pub description: String,
 
     // tag 名称
     pub name: String,
+
+    // 模块的目录名称
+    pub dir_name: String,
 }
 
 
@@ -0,0 +1,196 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use reqwest::Url;
+
+use super::{
+    document::DocumentFormat,
+    http_request::{self, HttpConfig},
+};
+
+/// 读取到的配置文件
+#[derive(Debug, Clone)]
+pub struct ConfigContent {
+    pub content: String,
+    pub format: DocumentFormat,
+}
+
+/// open api 配置文件来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// http(s) 地址
+    Http(String),
+    /// 本地文件，支持相对路径、绝对路径以及 file:// 地址
+    File(PathBuf),
+    /// 标准输入，使用 `-` 指定
+    Stdin,
+}
+
+impl ConfigSource {
+    /// 根据 -c 参数自动识别配置文件来源
+    pub fn detect(path: &str) -> ConfigSource {
+        let path = path.trim();
+        if path == "-" {
+            return ConfigSource::Stdin;
+        }
+        let lower_path = path.to_lowercase();
+        if lower_path.starts_with("http://") || lower_path.starts_with("https://") {
+            return ConfigSource::Http(path.to_string());
+        }
+        if lower_path.starts_with("file://") {
+            // file:///abs/path 形式交给 Url 解析，file://./relative 之类的写法直接去掉前缀
+            let file_path = Url::parse(path)
+                .ok()
+                .and_then(|url| url.to_file_path().ok())
+                .unwrap_or_else(|| PathBuf::from(&path["file://".len()..]));
+            return ConfigSource::File(file_path);
+        }
+        ConfigSource::File(PathBuf::from(path))
+    }
+
+    /// 解析相对于当前文档的地址，用于加载 $ref 引用的外部文档
+    pub fn join(&self, reference: &str) -> ConfigSource {
+        let lower_reference = reference.to_lowercase();
+        if ["http://", "https://", "file://"]
+            .iter()
+            .any(|v| lower_reference.starts_with(v))
+        {
+            return ConfigSource::detect(reference);
+        }
+        match self {
+            ConfigSource::Http(url) => Url::parse(url)
+                .and_then(|url| url.join(reference))
+                .map(|url| ConfigSource::Http(url.to_string()))
+                .unwrap_or_else(|_| ConfigSource::Http(reference.to_string())),
+            ConfigSource::File(path) => ConfigSource::File(
+                path.parent()
+                    .unwrap_or_else(|| Path::new(""))
+                    .join(reference),
+            ),
+            // 标准输入中的相对地址相对于当前工作目录
+            ConfigSource::Stdin => ConfigSource::File(PathBuf::from(reference)),
+        }
+    }
+
+    /// 文档地址，同一个文档的地址相同
+    pub fn location(&self) -> String {
+        match self {
+            ConfigSource::Http(url) => url.to_string(),
+            ConfigSource::File(path) => fs::canonicalize(path)
+                .unwrap_or_else(|_| path.clone())
+                .to_string_lossy()
+                .to_string(),
+            ConfigSource::Stdin => String::from("-"),
+        }
+    }
+
+    /// 读取配置文件内容并识别文件格式
+    pub async fn read(&self, http_config: &HttpConfig) -> Result<ConfigContent> {
+        let (content, content_type, path) = match self {
+            ConfigSource::Http(url) => {
+                let (content, content_type) = http_request::get_text(url, http_config)
+                    .await
+                    .with_context(|| format!("request {} error", url))?;
+                (content, content_type, url.to_string())
+            }
+            ConfigSource::File(path) => {
+                let content = fs::read_to_string(path)
+                    .with_context(|| format!("read {} error", path.display()))?;
+                (content, None, path.to_string_lossy().to_string())
+            }
+            ConfigSource::Stdin => {
+                let mut content = String::new();
+                io::stdin()
+                    .read_to_string(&mut content)
+                    .context("read stdin error")?;
+                (content, None, String::new())
+            }
+        };
+        let format = DocumentFormat::detect(content_type.as_deref(), &path, &content);
+        Ok(ConfigContent { content, format })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_test() {
+        let cases = [
+            (
+                "http://example.com/api.json",
+                ConfigSource::Http(String::from("http://example.com/api.json")),
+            ),
+            (
+                "HTTPS://example.com/api.yaml",
+                ConfigSource::Http(String::from("HTTPS://example.com/api.yaml")),
+            ),
+            (
+                "file:///tmp/api.json",
+                ConfigSource::File(PathBuf::from("/tmp/api.json")),
+            ),
+            (
+                "file://./api.json",
+                ConfigSource::File(PathBuf::from("./api.json")),
+            ),
+            (
+                "specs/api.yaml",
+                ConfigSource::File(PathBuf::from("specs/api.yaml")),
+            ),
+            (" - ", ConfigSource::Stdin),
+            (
+                r"C:\specs\api.json",
+                ConfigSource::File(PathBuf::from(r"C:\specs\api.json")),
+            ),
+        ];
+        for (path, source) in cases {
+            assert_eq!(ConfigSource::detect(path), source, "{}", path);
+        }
+    }
+
+    #[test]
+    fn join_test() {
+        let http = ConfigSource::Http(String::from("https://example.com/specs/api.json"));
+        let file = ConfigSource::File(PathBuf::from("specs/api.json"));
+        let cases = [
+            (
+                &http,
+                "user.json",
+                ConfigSource::Http(String::from("https://example.com/specs/user.json")),
+            ),
+            (
+                &http,
+                "../common/user.json",
+                ConfigSource::Http(String::from("https://example.com/common/user.json")),
+            ),
+            (
+                &file,
+                "user.json",
+                ConfigSource::File(PathBuf::from("specs/user.json")),
+            ),
+            (
+                &file,
+                "https://example.com/user.json",
+                ConfigSource::Http(String::from("https://example.com/user.json")),
+            ),
+            (
+                &file,
+                "file:///tmp/user.json",
+                ConfigSource::File(PathBuf::from("/tmp/user.json")),
+            ),
+            (
+                &ConfigSource::Stdin,
+                "user.json",
+                ConfigSource::File(PathBuf::from("user.json")),
+            ),
+        ];
+        for (source, reference, joined) in cases {
+            assert_eq!(source.join(reference), joined, "{:?} {}", source, reference);
+        }
+    }
+}
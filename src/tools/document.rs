@@ -0,0 +1,142 @@
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+
+/// 配置文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentFormat {
+    Json,
+    Yaml,
+}
+
+impl fmt::Display for DocumentFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DocumentFormat::Json => write!(f, "JSON"),
+            DocumentFormat::Yaml => write!(f, "YAML"),
+        }
+    }
+}
+
+impl DocumentFormat {
+    /// 识别配置文件格式
+    ///
+    /// 依次根据 content type、文件扩展名判断，都无法判断时根据文件内容的首个字符判断
+    pub fn detect(content_type: Option<&str>, path: &str, content: &str) -> DocumentFormat {
+        if let Some(format) = content_type.and_then(DocumentFormat::from_content_type) {
+            return format;
+        }
+        if let Some(format) = DocumentFormat::from_path(path) {
+            return format;
+        }
+        DocumentFormat::sniff(content)
+    }
+
+    fn from_content_type(content_type: &str) -> Option<DocumentFormat> {
+        let content_type = content_type.to_lowercase();
+        if content_type.contains("yaml") || content_type.contains("yml") {
+            Some(DocumentFormat::Yaml)
+        } else if content_type.contains("json") {
+            Some(DocumentFormat::Json)
+        } else {
+            None
+        }
+    }
+
+    fn from_path(path: &str) -> Option<DocumentFormat> {
+        // 去掉 url 中的 query 和 fragment
+        let path = path.split(['?', '#']).next().unwrap_or_default().to_lowercase();
+        if path.ends_with(".yaml") || path.ends_with(".yml") {
+            Some(DocumentFormat::Yaml)
+        } else if path.ends_with(".json") {
+            Some(DocumentFormat::Json)
+        } else {
+            None
+        }
+    }
+
+    fn sniff(content: &str) -> DocumentFormat {
+        match content.trim_start_matches('\u{feff}').trim_start().chars().next() {
+            Some('{') | Some('[') => DocumentFormat::Json,
+            _ => DocumentFormat::Yaml,
+        }
+    }
+}
+
+/// 将配置文件内容解析成指定类型
+///
+/// 解析失败时错误信息中包含出错的行号和列号
+pub fn parse_document<T: DeserializeOwned>(content: &str, format: DocumentFormat) -> Result<T> {
+    let content = content.trim_start_matches('\u{feff}');
+    match format {
+        DocumentFormat::Json => serde_json::from_str(content)
+            .map_err(|e| anyhow!("{} parse error: {}", format, e)),
+        DocumentFormat::Yaml => serde_yaml_ng::from_str(content)
+            .map_err(|e| anyhow!("{} parse error: {}", format, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_test() {
+        let cases = [
+            // content type 优先
+            (
+                Some("application/json"),
+                "api.yaml",
+                "openapi: 3.0.0",
+                DocumentFormat::Json,
+            ),
+            (
+                Some("application/x-yaml; charset=utf-8"),
+                "",
+                "{}",
+                DocumentFormat::Yaml,
+            ),
+            (
+                Some("text/plain"),
+                "api.json",
+                "openapi: 3.0.0",
+                DocumentFormat::Json,
+            ),
+            // 扩展名
+            (None, "api.yml", "{}", DocumentFormat::Yaml),
+            (
+                None,
+                "https://example.com/api.YAML?v=1#top",
+                "{}",
+                DocumentFormat::Yaml,
+            ),
+            (
+                None,
+                r"C:\specs\api.json",
+                "openapi: 3.0.0",
+                DocumentFormat::Json,
+            ),
+            // 文件内容
+            (
+                None,
+                "",
+                "\u{feff}  {\"openapi\": \"3.0.0\"}",
+                DocumentFormat::Json,
+            ),
+            (None, "api", "[]", DocumentFormat::Json),
+            (None, "api", "openapi: 3.0.0", DocumentFormat::Yaml),
+            (None, "", "", DocumentFormat::Yaml),
+        ];
+        for (content_type, path, content, format) in cases {
+            assert_eq!(
+                DocumentFormat::detect(content_type, path, content),
+                format,
+                "{:?} {} {}",
+                content_type,
+                path,
+                content
+            );
+        }
+    }
+}
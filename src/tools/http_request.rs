@@ -1,10 +1,163 @@
-use reqwest::Client;
-use serde::de::DeserializeOwned;
-
-pub async fn get<T: DeserializeOwned>(url: &str) -> Result<T, reqwest::Error> {
-    let client = Client::builder()
-        .danger_accept_invalid_certs(true)
-        .build()?;
-    let res = client.get(url).send().await?.json::<T>().await?;
-    Ok(res)
+use std::{env, fs, path::PathBuf, time::Duration};
+
+use anyhow::{anyhow, Context, Result};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE},
+    Certificate, Client, Proxy, Url,
+};
+
+/// 获取配置文件时使用的 http 配置
+///
+/// 认证信息从环境变量中读取，避免出现在命令行历史中
+#[derive(Debug, Clone, Default)]
+pub struct HttpConfig {
+    /// 自定义请求头 格式为 `Name: Value`
+    pub headers: Vec<String>,
+
+    /// 保存 bearer token 的环境变量名称
+    pub bearer_token_env: Option<String>,
+
+    /// 保存 basic auth 信息的环境变量名称 变量值格式为 `user:password`
+    pub basic_auth_env: Option<String>,
+
+    /// 自定义 CA 证书文件（PEM 格式，可包含多个证书）
+    pub ca_cert: Option<PathBuf>,
+
+    pub proxy: Option<String>,
+
+    pub timeout: Option<Duration>,
+
+    /// 跳过证书校验
+    pub insecure: bool,
+}
+
+impl HttpConfig {
+    /// 去掉认证信息与自定义请求头的配置，用于请求与根文档不同源的地址
+    pub fn without_credentials(&self) -> HttpConfig {
+        HttpConfig {
+            headers: vec![],
+            bearer_token_env: None,
+            basic_auth_env: None,
+            ..self.clone()
+        }
+    }
+}
+
+/// 判断两个地址是否同源（协议、主机以及端口都相同）
+pub fn is_same_origin(url: &str, other_url: &str) -> bool {
+    match (Url::parse(url), Url::parse(other_url)) {
+        (Ok(url), Ok(other_url)) => {
+            url.scheme() == other_url.scheme()
+                && url.host_str() == other_url.host_str()
+                && url.port_or_known_default() == other_url.port_or_known_default()
+        }
+        _ => false,
+    }
+}
+
+/// 获取地址对应的文本内容以及响应的 content type
+pub async fn get_text(url: &str, http_config: &HttpConfig) -> Result<(String, Option<String>)> {
+    let client = create_client(http_config)?;
+    let mut request = client.get(url).headers(create_headers(http_config)?);
+    if let Some(token_env) = &http_config.bearer_token_env {
+        request = request.bearer_auth(read_env(token_env)?);
+    }
+    if let Some(basic_auth_env) = &http_config.basic_auth_env {
+        let basic_auth = read_env(basic_auth_env)?;
+        request = match basic_auth.split_once(':') {
+            Some((user, password)) => request.basic_auth(user, Some(password)),
+            None => request.basic_auth(basic_auth, None::<&str>),
+        };
+    }
+    let res = request.send().await?.error_for_status()?;
+    let content_type = res
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+    Ok((res.text().await?, content_type))
+}
+
+fn create_client(http_config: &HttpConfig) -> Result<Client> {
+    let mut builder = Client::builder().danger_accept_invalid_certs(http_config.insecure);
+    if let Some(ca_cert) = &http_config.ca_cert {
+        let pem = fs::read(ca_cert)
+            .with_context(|| format!("read ca cert {} error", ca_cert.display()))?;
+        for certificate in Certificate::from_pem_bundle(&pem)
+            .with_context(|| format!("parse ca cert {} error", ca_cert.display()))?
+        {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    if let Some(proxy) = &http_config.proxy {
+        builder = builder.proxy(Proxy::all(proxy).with_context(|| format!("proxy {} error", proxy))?);
+    }
+    if let Some(timeout) = http_config.timeout {
+        builder = builder.timeout(timeout);
+    }
+    Ok(builder.build()?)
+}
+
+fn create_headers(http_config: &HttpConfig) -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    for header in &http_config.headers {
+        let (name, value) = header
+            .split_once(':')
+            .ok_or_else(|| anyhow!("header {} should be `Name: Value`", header))?;
+        headers.append(
+            HeaderName::from_bytes(name.trim().as_bytes())
+                .with_context(|| format!("header name {} error", name))?,
+            HeaderValue::from_str(value.trim())
+                .with_context(|| format!("header value of {} error", name))?,
+        );
+    }
+    Ok(headers)
+}
+
+fn read_env(name: &str) -> Result<String> {
+    env::var(name).with_context(|| format!("environment variable {} is not set", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_same_origin_test() {
+        assert!(is_same_origin(
+            "https://example.com/api/openapi.json",
+            "https://example.com:443/common/money.yaml"
+        ));
+        assert!(!is_same_origin(
+            "https://example.com/api/openapi.json",
+            "http://example.com/api/money.yaml"
+        ));
+        assert!(!is_same_origin(
+            "https://example.com/api/openapi.json",
+            "https://cdn.example.com/api/money.yaml"
+        ));
+        assert!(!is_same_origin(
+            "https://example.com/api/openapi.json",
+            "https://example.com:8443/api/money.yaml"
+        ));
+        assert!(!is_same_origin("/specs/openapi.json", "https://example.com/api/money.yaml"));
+    }
+
+    #[test]
+    fn without_credentials_test() {
+        let http_config = HttpConfig {
+            headers: vec![String::from("X-Api-Key: xxx")],
+            bearer_token_env: Some(String::from("TOKEN")),
+            basic_auth_env: Some(String::from("BASIC_AUTH")),
+            proxy: Some(String::from("http://127.0.0.1:8080")),
+            insecure: true,
+            ..Default::default()
+        }
+        .without_credentials();
+        assert!(http_config.headers.is_empty());
+        assert!(http_config.bearer_token_env.is_none());
+        assert!(http_config.basic_auth_env.is_none());
+        assert_eq!(http_config.proxy.as_deref(), Some("http://127.0.0.1:8080"));
+        assert!(http_config.insecure);
+    }
 }
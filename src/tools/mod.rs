@@ -1,2 +1,8 @@
+pub mod config_source;
+pub mod document;
 pub mod http_request;
-pub mod tools;
\ No newline at end of file
+pub mod module_name;
+#[allow(clippy::module_inception)]
+pub mod tools;
+pub mod ts_escape;
+pub mod type_mapping;
\ No newline at end of file
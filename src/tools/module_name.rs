@@ -0,0 +1,140 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use anyhow::{Context, Result};
+
+use super::{
+    document::{parse_document, DocumentFormat},
+    tools::capitalize,
+};
+
+/// windows 中不能作为文件名的名称
+const WINDOWS_RESERVED_NAMES: [&str; 22] = [
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// 读取 tag 名称与模块目录名称的对应关系，支持 json、yaml
+pub fn load_module_name_map(path: &Path) -> Result<HashMap<String, String>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("read module name map {} error", path.display()))?;
+    let format = DocumentFormat::detect(None, &path.to_string_lossy(), &content);
+    parse_document(&content, format)
+        .with_context(|| format!("parse module name map {} error", path.display()))
+}
+
+/// 将名称拆分为单词，非字母数字字符以及大小写变化处作为分隔
+///
+/// 如：UserHTTPController 则返回 ["User", "HTTP", "Controller"]
+fn split_words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words: Vec<String> = vec![];
+    let mut word = String::new();
+    for (index, c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        if let Some(prev) = word.chars().last() {
+            let next_is_lower = matches!(chars.get(index + 1), Some(v) if v.is_lowercase());
+            if c.is_uppercase()
+                && (prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower))
+            {
+                words.push(std::mem::take(&mut word));
+            }
+        }
+        word.push(*c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// 转换为短横线连接的小写名称，如：UserController 则返回 user-controller
+pub fn to_kebab_case(name: &str) -> String {
+    split_words(name)
+        .iter()
+        .map(|v| v.to_lowercase())
+        .collect::<Vec<String>>()
+        .join("-")
+}
+
+/// 转换为小驼峰名称，如：user-controller 则返回 userController
+pub fn to_camel_case(name: &str) -> String {
+    split_words(name)
+        .iter()
+        .enumerate()
+        .map(|(index, word)| {
+            let word = word.to_lowercase();
+            if index == 0 {
+                word
+            } else {
+                capitalize(&word)
+            }
+        })
+        .collect()
+}
+
+/// 去掉文件名中不安全的字符
+///
+/// 去掉路径分隔符、windows 中不能使用的字符、引号、控制字符以及首尾的 . 和空白，
+/// windows 保留的名称添加 _ 后缀
+pub fn sanitize_file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .filter(|c| !c.is_control() && !"<>:\"'`/\\|?*".contains(*c))
+        .collect();
+    let name = name.trim_matches(|c: char| c == '.' || c.is_whitespace()).to_string();
+    if WINDOWS_RESERVED_NAMES.contains(&name.to_lowercase().as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_words_test() {
+        assert_eq!(
+            split_words("UserHTTPController"),
+            vec!["User", "HTTP", "Controller"]
+        );
+        assert_eq!(
+            split_words("user-controller_v2"),
+            vec!["user", "controller", "v2"]
+        );
+        assert_eq!(split_words("order2Item"), vec!["order2", "Item"]);
+        assert_eq!(split_words("用户 管理"), vec!["用户", "管理"]);
+        assert!(split_words(" - ").is_empty());
+    }
+
+    #[test]
+    fn to_kebab_case_test() {
+        assert_eq!(to_kebab_case("UserController"), "user-controller");
+        assert_eq!(to_kebab_case("user controller"), "user-controller");
+    }
+
+    #[test]
+    fn to_camel_case_test() {
+        assert_eq!(to_camel_case("user-controller"), "userController");
+        assert_eq!(to_camel_case("UserHTTPController"), "userHttpController");
+    }
+
+    #[test]
+    fn sanitize_file_name_test() {
+        assert_eq!(sanitize_file_name("user/admin"), "useradmin");
+        assert_eq!(sanitize_file_name("..\\secret"), "secret");
+        assert_eq!(sanitize_file_name(" 用户管理. "), "用户管理");
+        assert_eq!(sanitize_file_name("a<b>:\"c\"|d?*"), "abcd");
+        assert_eq!(sanitize_file_name("tab\tname"), "tabname");
+        assert_eq!(sanitize_file_name("CON"), "CON_");
+        assert_eq!(sanitize_file_name("lpt1"), "lpt1_");
+        assert_eq!(sanitize_file_name("console"), "console");
+    }
+}
@@ -5,4 +5,54 @@ pub fn capitalize(s: &str) -> String {
         None => String::new(),
         Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
     }
-}
\ No newline at end of file
+}
+
+/// 获取不冲突的名称
+///
+/// 名称已被使用时按照后缀模板生成名称，{name} 替换为原名称，{index} 替换为从 2 开始的序号
+pub fn get_unique_name(
+    name: &str,
+    suffix_template: &str,
+    is_taken: &dyn Fn(&str) -> bool,
+) -> String {
+    if !is_taken(name) {
+        return name.to_string();
+    }
+    let mut index = 2;
+    loop {
+        let unique_name = suffix_template
+            .replace("{name}", name)
+            .replace("{index}", &index.to_string());
+        if !is_taken(&unique_name) {
+            return unique_name;
+        }
+        index += 1;
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capitalize_test() {
+        assert_eq!(capitalize("user"), "User");
+        assert_eq!(capitalize(""), "");
+    }
+
+    #[test]
+    fn get_unique_name_test() {
+        let taken = ["User", "User2", "User_2"];
+        let is_taken = |v: &str| taken.contains(&v);
+        assert_eq!(
+            get_unique_name("Order", "{name}{index}", &is_taken),
+            "Order"
+        );
+        assert_eq!(get_unique_name("User", "{name}{index}", &is_taken), "User3");
+        assert_eq!(
+            get_unique_name("User", "{name}_{index}", &is_taken),
+            "User_3"
+        );
+    }
+}
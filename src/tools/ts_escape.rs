@@ -0,0 +1,148 @@
+/// typescript 中不能作为标识符的关键字
+const TS_RESERVED_WORDS: [&str; 46] = [
+    "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete",
+    "do", "else", "enum", "export", "extends", "false", "finally", "for", "function", "if",
+    "import", "in", "instanceof", "new", "null", "return", "super", "switch", "this", "throw",
+    "true", "try", "typeof", "var", "void", "while", "with", "let", "static", "yield",
+    "implements", "interface", "package", "private", "protected", "public", "await",
+];
+
+/// typescript 中不能作为类型名称的内置类型
+const TS_TYPE_RESERVED_WORDS: [&str; 10] = [
+    "any", "bigint", "boolean", "never", "number", "object", "string", "symbol", "undefined",
+    "unknown",
+];
+
+/// 判断字符是否可以出现在标识符中
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// 标识符为空、以数字开头或与关键字重名时添加 _ 前缀
+fn fix_identifier(identifier: String, reserved_words: &[&str]) -> String {
+    if identifier.is_empty()
+        || identifier.starts_with(|c: char| c.is_ascii_digit())
+        || TS_RESERVED_WORDS.contains(&identifier.as_str())
+        || reserved_words.contains(&identifier.as_str())
+    {
+        format!("_{}", identifier)
+    } else {
+        identifier
+    }
+}
+
+/// 将非法字符分隔的单词拼接为驼峰，首字母大小写保持不变
+///
+/// 如：get-user-info 则返回 getUserInfo
+pub fn join_identifier_words(name: &str) -> String {
+    let mut identifier = String::new();
+    let mut upper = false;
+    for c in name.chars() {
+        if is_identifier_char(c) {
+            if upper && !identifier.is_empty() {
+                identifier.extend(c.to_uppercase());
+            } else {
+                identifier.push(c);
+            }
+            upper = false;
+        } else {
+            upper = true;
+        }
+    }
+    identifier
+}
+
+/// 生成合法的 typescript 标识符，用于函数名、变量名
+///
+/// 如：get-user 则返回 getUser，delete 则返回 _delete，1st 则返回 _1st
+pub fn ts_identifier(name: &str) -> String {
+    fix_identifier(join_identifier_words(name), &[])
+}
+
+/// 生成合法的 typescript 类型名称，去掉名称中的非法字符
+///
+/// 如：Result«User» 则返回 ResultUser，string 则返回 _string
+pub fn ts_type_identifier(name: &str) -> String {
+    fix_identifier(
+        name.chars().filter(|c| is_identifier_char(*c)).collect(),
+        &TS_TYPE_RESERVED_WORDS,
+    )
+}
+
+/// 转换为 typescript 字符串字面量
+pub fn ts_string_literal(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| format!("\"{}\"", value))
+}
+
+/// 转义模板字符串中的特殊字符
+pub fn escape_template_literal(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('`', "\\`")
+        .replace("${", "\\${")
+}
+
+/// 转义 JSDoc 注释中的内容
+///
+/// */ 替换为 *\/，多行内容的每一行添加 indent + * 前缀
+pub fn escape_doc_comment(text: &str, indent: &str) -> String {
+    text.replace("*/", "*\\/")
+        .lines()
+        .collect::<Vec<&str>>()
+        .join(&format!("\n{} * ", indent))
+}
+
+/// 转义单行注释中的内容，换行替换为空格
+pub fn escape_line_comment(text: &str) -> String {
+    text.lines().collect::<Vec<&str>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ts_identifier_test() {
+        assert_eq!(ts_identifier("get-user-info"), "getUserInfo");
+        assert_eq!(ts_identifier("GetUser"), "GetUser");
+        assert_eq!(ts_identifier("delete"), "_delete");
+        assert_eq!(ts_identifier("interface"), "_interface");
+        assert_eq!(ts_identifier("await"), "_await");
+        assert_eq!(ts_identifier("1st"), "_1st");
+        assert_eq!(ts_identifier("--"), "_");
+    }
+
+    #[test]
+    fn ts_type_identifier_test() {
+        assert_eq!(ts_type_identifier("Result«User»"), "ResultUser");
+        assert_eq!(ts_type_identifier("string"), "_string");
+        assert_eq!(ts_type_identifier("public"), "_public");
+        assert_eq!(ts_type_identifier("User"), "User");
+    }
+
+    #[test]
+    fn ts_string_literal_test() {
+        assert_eq!(ts_string_literal("name"), "\"name\"");
+        assert_eq!(ts_string_literal("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+    }
+
+    #[test]
+    fn escape_template_literal_test() {
+        assert_eq!(escape_template_literal("/users/${id}"), "/users/\\${id}");
+        assert_eq!(escape_template_literal("a`b\\c"), "a\\`b\\\\c");
+    }
+
+    #[test]
+    fn escape_doc_comment_test() {
+        assert_eq!(escape_doc_comment("a */ b", "  "), "a *\\/ b");
+        assert_eq!(
+            escape_doc_comment("line1\nline2", "  "),
+            "line1\n   * line2"
+        );
+    }
+
+    #[test]
+    fn escape_line_comment_test() {
+        assert_eq!(escape_line_comment("line1\nline2"), "line1 line2");
+    }
+}
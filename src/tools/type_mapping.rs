@@ -0,0 +1,70 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::document::{parse_document, DocumentFormat};
+
+/// 用户自定义的类型映射
+///
+/// key 为 `type/format` 或 `type`，如：string/date-time、integer/int64
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum TypeMapping {
+    /// 只声明 ts 类型，如："Date"
+    Type(String),
+    Detail {
+        /// ts 类型
+        #[serde(rename = "type")]
+        ts_type: String,
+
+        /// 响应值的转换表达式，使用 value 表示原始值，如：new Date(value)
+        convert: Option<String>,
+
+        /// 写入 api.d.ts 的类型声明，如：type UUID = string & { readonly __brand: "UUID" };
+        declare: Option<String>,
+    },
+}
+
+impl TypeMapping {
+    pub fn ts_type(&self) -> &str {
+        match self {
+            TypeMapping::Type(ts_type) => ts_type,
+            TypeMapping::Detail { ts_type, .. } => ts_type,
+        }
+    }
+
+    /// 获取响应值的转换表达式
+    ///
+    /// 未声明时 Date 与 bigint 使用内置的转换表达式
+    pub fn convert(&self) -> Option<String> {
+        if let TypeMapping::Detail {
+            convert: Some(convert),
+            ..
+        } = self
+        {
+            return Some(convert.to_string());
+        }
+        match self.ts_type() {
+            "Date" => Some(String::from("new Date(value)")),
+            "bigint" => Some(String::from("BigInt(value)")),
+            _ => None,
+        }
+    }
+
+    pub fn declare(&self) -> Option<&str> {
+        match self {
+            TypeMapping::Type(_) => None,
+            TypeMapping::Detail { declare, .. } => declare.as_deref(),
+        }
+    }
+}
+
+/// 读取类型映射文件 支持 json 与 yaml
+pub fn load_type_mapping(path: &Path) -> Result<HashMap<String, TypeMapping>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("read type mapping {} error", path.display()))?;
+    let format = DocumentFormat::detect(None, &path.to_string_lossy(), &content);
+    parse_document(&content, format)
+        .with_context(|| format!("parse type mapping {} error", path.display()))
+}